@@ -1,5 +1,5 @@
 /// A simple struct representing a rectangular area.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Area {
     /// Start X coordinate.
     pub x: u16,
@@ -41,6 +41,27 @@ impl Area {
         debug_assert!(self.w > 0 && self.h > 0);
         (self.x + self.w - 1, self.y + self.h - 1)
     }
+
+    /// Returns the smallest area containing `self` whose position and size are multiples of
+    /// `x_align` and `y_align`.
+    pub const fn expand_to_alignment(&self, x_align: u16, y_align: u16) -> Self {
+        let x = self.x - self.x % x_align;
+        let y = self.y - self.y % y_align;
+        Self {
+            x,
+            y,
+            w: (self.x + self.w - x).next_multiple_of(x_align),
+            h: (self.y + self.h - y).next_multiple_of(y_align),
+        }
+    }
+
+    /// Returns whether `self` lies completely inside `other`.
+    pub const fn is_within(&self, other: &Area) -> bool {
+        self.x >= other.x
+            && self.y >= other.y
+            && self.x as u32 + self.w as u32 <= other.x as u32 + other.w as u32
+            && self.y as u32 + self.h as u32 <= other.y as u32 + other.h as u32
+    }
 }
 
 // #[derive(Clone, Copy, Debug)]
@@ -86,3 +107,19 @@ mod eg_impls {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_to_alignment() {
+        let area = Area::new(3, 5, 4, 1);
+        assert_eq!(area.expand_to_alignment(2, 2), Area::new(2, 4, 6, 2));
+        assert_eq!(area.expand_to_alignment(1, 1), area);
+        assert_eq!(area.expand_to_alignment(4, 8), Area::new(0, 0, 8, 8));
+
+        let aligned = Area::new(4, 2, 6, 2);
+        assert_eq!(aligned.expand_to_alignment(2, 2), aligned);
+    }
+}
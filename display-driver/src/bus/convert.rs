@@ -0,0 +1,340 @@
+use crate::{
+    color::{pack_pixel, DitherMethod, Ditherer},
+    Area, ColorFormat, SolidColor,
+};
+
+use super::{
+    BusHardwareFill, BusRead, DisplayBus, DisplayError, ErrorType, Metadata, PixelByteOrder,
+};
+
+/// An adapter that converts pixel data between RGB color formats while it is streamed.
+///
+/// This lets the framebuffer format differ from the panel interface format, e.g. rendering in
+/// RGB565 for a panel that only accepts RGB666 over SPI. The pixel data of each
+/// [`write_pixels`](DisplayBus::write_pixels) call is converted from `source` to `target` in
+/// chunks of up to `N` bytes, which are forwarded to the inner bus. The [`Metadata`] of the call
+/// is split accordingly: the area and `first` flag go with the first chunk, the `last` flag with
+/// the last one.
+///
+/// Conversion from RGB332, RGB565, RGB666 and RGB888 to any RGB format is supported; other
+/// formats, or equal `source` and `target` formats, pass through unchanged. The panel must be set
+/// to the `target` format, e.g. with `DisplayDriverBuilder::with_color_format`. The driver then
+/// reports `source` as its [`color_format`](crate::DisplayDriver::color_format) and encodes the
+/// pixel data it generates (clear color, fills, `write_pixels_iter`) in `source` too, see
+/// [`DisplayBus::pixel_data_format`]. For a packed target (RGB444), each `write_pixels` call must
+/// hold an even number of pixels.
+///
+/// When reducing the color depth, e.g. from RGB888 to RGB565 or RGB444, the colors are rounded to
+/// the nearest level, or dithered with [`with_dither`](Self::with_dither). The pixel positions
+/// are tracked from the window in the [`Metadata`]. `W` is the width covered by the error
+/// diffusion buffer, see [`Ditherer`]. Hardware fills are rounded, not dithered.
+///
+/// # Example
+/// ```ignore
+/// let bus = ColorConvertBus::<_, 240>::new(spi_bus, ColorFormat::RGB565, ColorFormat::RGB666);
+/// let mut display = DisplayDriver::builder(bus, panel)
+///     .with_color_format(ColorFormat::RGB666)
+///     .init(&mut delay).await.unwrap();
+///
+/// // RGB565 data, sent as RGB666
+/// display.write_frame(framebuffer.data()).await.unwrap();
+/// display.fill_screen_chunked::<512>(Rgb565::BLACK.into()).await.unwrap();
+/// ```
+pub struct ColorConvertBus<B: DisplayBus, const N: usize = 256, const W: usize = 0> {
+    inner: B,
+    source: ColorFormat,
+    target: ColorFormat,
+    dither: DitherMethod,
+    ditherer: Option<Ditherer<W>>,
+    /// The current window and the position of the next pixel in it, for dithering.
+    window: Area,
+    position: (u16, u16),
+}
+
+impl<B: DisplayBus, const N: usize, const W: usize> ColorConvertBus<B, N, W> {
+    /// Creates a new wrapper converting pixel data from `source` to `target`.
+    pub fn new(inner: B, source: ColorFormat, target: ColorFormat) -> Self {
+        Self {
+            inner,
+            source,
+            target,
+            dither: DitherMethod::None,
+            ditherer: Ditherer::new(DitherMethod::None, target),
+            window: Area::from_origin(0, 0),
+            position: (0, 0),
+        }
+    }
+
+    /// Dithers the converted pixel data with `method`.
+    ///
+    /// Returns [`DisplayError::InvalidArgs`] for [`DitherMethod::ErrorDiffusion`] if `W` is 0.
+    pub fn with_dither(mut self, method: DitherMethod) -> Result<Self, DisplayError<B::Error>> {
+        self.set_dither(method)?;
+        Ok(self)
+    }
+
+    /// Changes the dithering method.
+    ///
+    /// Returns [`DisplayError::InvalidArgs`] for [`DitherMethod::ErrorDiffusion`] if `W` is 0.
+    pub fn set_dither(&mut self, method: DitherMethod) -> Result<(), DisplayError<B::Error>> {
+        if method == DitherMethod::ErrorDiffusion && W == 0 {
+            return Err(DisplayError::InvalidArgs);
+        }
+        self.dither = method;
+        self.ditherer = Ditherer::new(method, self.target);
+        Ok(())
+    }
+
+    /// Changes the formats to convert between.
+    pub fn set_formats(&mut self, source: ColorFormat, target: ColorFormat) {
+        self.source = source;
+        self.target = target;
+        self.ditherer = Ditherer::new(self.dither, target);
+    }
+
+    /// Returns the source (framebuffer) format.
+    pub fn source(&self) -> ColorFormat {
+        self.source
+    }
+
+    /// Returns the target (panel interface) format.
+    pub fn target(&self) -> ColorFormat {
+        self.target
+    }
+
+    /// Returns the inner bus.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
+    /// Returns whether pixel data has to be converted.
+    fn converts(&self) -> bool {
+        self.source != self.target
+            && self.source.is_rgb()
+            && !self.source.is_packed()
+            && self.target.is_rgb()
+    }
+
+    /// Returns the position of the next pixel and advances it in the window.
+    fn next_position(&mut self) -> (u16, u16) {
+        let (x, y) = self.position;
+        self.position = if x + 1 >= self.window.x + self.window.w {
+            (self.window.x, y + 1)
+        } else {
+            (x + 1, y)
+        };
+        (x, y)
+    }
+
+    /// Converts the raw bytes of `color` to the target format, rounding to the nearest level.
+    fn convert_color(&self, color: SolidColor) -> SolidColor {
+        let rounding = Ditherer::<0>::new(DitherMethod::None, self.target);
+        self.source
+            .decode_rgb(&color.raw)
+            .zip(rounding)
+            .and_then(|(rgb, mut rounding)| {
+                SolidColor::from_rgb888(self.target, rounding.dither(0, 0, rgb))
+            })
+            .unwrap_or(color)
+    }
+}
+
+impl<B: DisplayBus, const N: usize, const W: usize> ErrorType for ColorConvertBus<B, N, W> {
+    type Error = B::Error;
+}
+
+impl<B: DisplayBus, const N: usize, const W: usize> DisplayBus for ColorConvertBus<B, N, W> {
+    const HARDWARE_BYTE_SWAP: bool = B::HARDWARE_BYTE_SWAP;
+
+    async fn write_cmd(&mut self, cmd: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write_cmd(cmd).await
+    }
+
+    async fn write_cmd_with_params(
+        &mut self,
+        cmd: &[u8],
+        params: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.inner.write_cmd_with_params(cmd, params).await
+    }
+
+    async fn write_pixels(
+        &mut self,
+        cmd: &[u8],
+        data: &[u8],
+        metadata: Metadata,
+    ) -> Result<(), DisplayError<Self::Error>> {
+        if !self.converts() {
+            return self.inner.write_pixels(cmd, data, metadata).await;
+        }
+
+        let source_size = self.source.size_bytes() as usize;
+        let target_bits = self.target.wire_bits() as usize;
+        let (pattern_bytes, pattern_pixels) = self.target.pattern_size();
+        let pixels_per_chunk = N / pattern_bytes * pattern_pixels;
+        if pixels_per_chunk == 0 || !data.len().is_multiple_of(source_size) {
+            return Err(DisplayError::InvalidArgs);
+        }
+        if let Some(area) = metadata.area {
+            self.window = area;
+            self.position = (area.x, area.y);
+        }
+
+        // Little-endian data only arrives here if the inner bus swaps in hardware; the converted
+        // data is big-endian, so the swap is done while decoding instead.
+        let swap = metadata.byte_order == PixelByteOrder::LittleEndian && source_size == 2;
+
+        let mut buffer = [0u8; N];
+        let mut chunks = data.chunks(pixels_per_chunk * source_size).peekable();
+        let mut chunk_metadata = metadata;
+        chunk_metadata.byte_order = PixelByteOrder::BigEndian;
+        while let Some(chunk) = chunks.next() {
+            let mut pixels = 0;
+            for pixel in chunk.chunks_exact(source_size) {
+                let swapped;
+                let pixel = if swap {
+                    swapped = [pixel[1], pixel[0]];
+                    &swapped[..]
+                } else {
+                    pixel
+                };
+                if let Some(mut rgb) = self.source.decode_rgb(pixel) {
+                    // Rounding doesn't depend on the position
+                    let (x, y) = match self.dither {
+                        DitherMethod::None => (0, 0),
+                        _ => self.next_position(),
+                    };
+                    if let Some(ditherer) = &mut self.ditherer {
+                        rgb = ditherer.dither(x, y, rgb);
+                    }
+                    let mut raw = [0u8; 3];
+                    self.target.encode_rgb(rgb, &mut raw);
+                    pack_pixel(&mut buffer, pixels * target_bits, &raw, target_bits);
+                }
+                pixels += 1;
+            }
+            let len = self.target.bytes_for(pixels);
+            chunk_metadata.frame_control.last =
+                metadata.frame_control.last && chunks.peek().is_none();
+
+            self.inner
+                .write_pixels(cmd, &buffer[..len], chunk_metadata)
+                .await?;
+            chunk_metadata = Metadata::new_continue_stream();
+        }
+        Ok(())
+    }
+
+    fn set_reset(&mut self, reset: bool) -> Result<(), DisplayError<Self::Error>> {
+        self.inner.set_reset(reset)
+    }
+
+    fn pixel_data_format(&self, panel_format: ColorFormat) -> ColorFormat {
+        let format = self.inner.pixel_data_format(panel_format);
+        if format == self.target && self.converts() {
+            self.source
+        } else {
+            format
+        }
+    }
+}
+
+impl<B: DisplayBus + BusHardwareFill, const N: usize, const W: usize> BusHardwareFill
+    for ColorConvertBus<B, N, W>
+{
+    async fn fill_solid(
+        &mut self,
+        cmd: &[u8],
+        color: SolidColor,
+        area: Area,
+    ) -> Result<(), DisplayError<Self::Error>> {
+        let color = if self.converts() && color.format == self.source {
+            self.convert_color(color)
+        } else {
+            color
+        };
+        self.inner.fill_solid(cmd, color, area).await
+    }
+}
+
+impl<B: DisplayBus + BusRead, const N: usize, const W: usize> BusRead for ColorConvertBus<B, N, W> {
+    async fn read_data(
+        &mut self,
+        cmd: &[u8],
+        params: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), DisplayError<Self::Error>> {
+        self.inner.read_data(cmd, params, buffer).await
+    }
+}
+
+#[cfg(all(test, feature = "embedded-graphics"))]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    extern crate std;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::{
+        test_util::{block_on, MockBus, MockPanel, NoDelay},
+        DisplayDriver, FrameControl,
+    };
+
+    #[test]
+    fn driver_pixels_are_converted_once() {
+        let bus = ColorConvertBus::<_, 16>::new(
+            MockBus::<false>::default(),
+            ColorFormat::RGB565,
+            ColorFormat::RGB666,
+        );
+        let mut driver = block_on(
+            DisplayDriver::builder(bus, MockPanel::new(4, 2))
+                .with_color_format(ColorFormat::RGB666)
+                .with_clear_color(Rgb565::WHITE.into())
+                .init(&mut NoDelay),
+        )
+        .unwrap();
+        assert_eq!(driver.color_format(), Some(ColorFormat::RGB565));
+        assert_eq!(driver.bus.inner.cmds[0], [0x3A, 18]);
+        assert_eq!(driver.bus.inner.pixels(), [0xFC; 8 * 3]);
+
+        driver.bus.inner.pixel_writes.clear();
+        let area = Area::from_origin(4, 2);
+        block_on(driver.write_pixels_iter::<16, _>(area, [Rgb565::RED; 8])).unwrap();
+        assert_eq!(driver.bus.inner.pixels(), [0xFC, 0, 0].repeat(8));
+    }
+
+    #[test]
+    fn splits_writes_into_chunks() {
+        let mut bus = ColorConvertBus::<_, 6>::new(
+            MockBus::<false>::default(),
+            ColorFormat::RGB565,
+            ColorFormat::RGB888,
+        );
+        let area = Area::from_origin(5, 1);
+        let data = [0xFF, 0xFF, 0, 0, 0xFF, 0xFF, 0, 0, 0xFF, 0xFF];
+        let metadata = Metadata::new_from_parts(Some(area), FrameControl::new_standalone());
+        block_on(bus.write_pixels(&[0x2C], &data, metadata)).unwrap();
+
+        let writes = &bus.inner.pixel_writes;
+        assert_eq!(writes.len(), 3);
+        assert_eq!(writes[0].data, [0xFF, 0xFF, 0xFF, 0, 0, 0]);
+        assert_eq!(writes[2].data, [0xFF; 3]);
+        let flags: Vec<_> = writes
+            .iter()
+            .map(|write| {
+                let FrameControl { first, last } = write.metadata.frame_control;
+                (write.metadata.area, first, last)
+            })
+            .collect();
+        assert_eq!(
+            flags,
+            [
+                (Some(area), true, false),
+                (None, false, false),
+                (None, false, true)
+            ]
+        );
+    }
+}
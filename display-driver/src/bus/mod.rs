@@ -1,13 +1,16 @@
 #[cfg(feature = "display-interface")]
 mod display_interface_impl;
 
+pub mod convert;
+pub use convert::ColorConvertBus;
+
 pub mod qspi_flash;
 pub use qspi_flash::QspiFlashBus;
 
 pub mod simple;
 pub use simple::SimpleDisplayBus;
 
-use crate::{Area, DisplayError, SolidColor};
+use crate::{Area, ColorFormat, DisplayError, SolidColor};
 
 /// Error type trait.
 ///
@@ -46,11 +49,23 @@ impl FrameControl {
     }
 }
 
+/// Byte order of the pixels in a pixel data buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PixelByteOrder {
+    /// Most significant byte first, as displays expect it (default).
+    #[default]
+    BigEndian,
+    /// Least significant byte first, as native RGB565 from many renderers (Slint, DMA2D).
+    ///
+    /// The two bytes of each 16-bit pixel have to be swapped before they reach the display.
+    LittleEndian,
+}
+
 /// Metadata about the pixel data transfer.
 ///
 /// Advanced display buses (like MIPI DSI or QSPI with DMA) often require more context than just the
 /// raw pixel bytes.
-/// This struct carries that side-band information, allowing the bus implementation to orchestrate 
+/// This struct carries that side-band information, allowing the bus implementation to orchestrate
 /// the transfer correctly.
 #[derive(Clone, Copy, Debug)]
 pub struct Metadata {
@@ -61,6 +76,12 @@ pub struct Metadata {
     pub area: Option<Area>,
     /// Flags for frame synchronization (start/end of frame).
     pub frame_control: FrameControl,
+    /// Byte order of the pixel data.
+    ///
+    /// Only [`PixelByteOrder::BigEndian`] is passed to buses without
+    /// [`HARDWARE_BYTE_SWAP`](DisplayBus::HARDWARE_BYTE_SWAP); the driver swaps in software for
+    /// them.
+    pub byte_order: PixelByteOrder,
 }
 
 impl Metadata {
@@ -76,6 +97,7 @@ impl Metadata {
                 first: true,
                 last: true,
             },
+            byte_order: PixelByteOrder::BigEndian,
         }
     }
 
@@ -90,6 +112,7 @@ impl Metadata {
                 first: false,
                 last: false,
             },
+            byte_order: PixelByteOrder::BigEndian,
         }
     }
 
@@ -100,6 +123,7 @@ impl Metadata {
         Self {
             area,
             frame_control,
+            byte_order: PixelByteOrder::BigEndian,
         }
     }
 }
@@ -114,13 +138,19 @@ impl Metadata {
 /// The interface distinguishes between two types of traffic:
 /// - **Commands**: Small, latency-sensitive messages used for configuration (handled by `write_cmd`
 ///   and `write_cmd_with_params`).
-/// - **Pixels**: Large, throughput-critical data streams used for changing the visual content 
+/// - **Pixels**: Large, throughput-critical data streams used for changing the visual content
 ///   (handled by `write_pixels`).
 ///
-/// This separation allows for optimizations. For instance, `write_pixels` accepts [`Metadata`], 
-/// enabling the underlying implementation to utilize hardware accelerators (like DMA or QSPI 
+/// This separation allows for optimizations. For instance, `write_pixels` accepts [`Metadata`],
+/// enabling the underlying implementation to utilize hardware accelerators (like DMA or QSPI
 /// peripherals) that can handle address setting and bulk data transfer efficiently.
 pub trait DisplayBus: ErrorType {
+    /// Whether [`write_pixels`](Self::write_pixels) honours [`Metadata::byte_order`], e.g. with
+    /// an SPI peripheral or DMA that can swap bytes.
+    ///
+    /// If `false` (default), the driver swaps little-endian pixel data in software.
+    const HARDWARE_BYTE_SWAP: bool = false;
+
     /// Writes a command to the display.
     ///
     /// This is typically used for setting registers or sending configuration opcodes.
@@ -130,7 +160,7 @@ pub trait DisplayBus: ErrorType {
 
     /// Writes a command followed immediately by its parameters.
     ///
-    /// This guarantees an atomic transaction where the command and parameters are sent without 
+    /// This guarantees an atomic transaction where the command and parameters are sent without
     /// interruption. This is critical for many display controllers that expect the parameter bytes
     /// to immediately follow the command byte while the Chip Select (CS) line remains active.
     async fn write_cmd_with_params(&mut self, cmd: &[u8], params: &[u8])
@@ -154,7 +184,7 @@ pub trait DisplayBus: ErrorType {
     ) -> Result<(), DisplayError<Self::Error>>;
 
     /// Resets the screen via the bus (optional).
-    /// 
+    ///
     /// Note: This method should only be implemented if the hardware has a physical Reset pin.
     /// Avoid adding a Pin field to your `DisplayBus` wrapper for this purpose; use `LCDResetOption`
     /// instead.
@@ -162,6 +192,16 @@ pub trait DisplayBus: ErrorType {
         let _ = reset;
         Err(DisplayError::Unsupported)
     }
+
+    /// Returns the color format [`write_pixels`](Self::write_pixels) expects when the panel is
+    /// set to `panel_format`.
+    ///
+    /// This is `panel_format` (default), unless the bus converts the pixel data on the way, see
+    /// [`ColorConvertBus`]. The driver encodes the pixel data it generates (fills, colors) in this
+    /// format.
+    fn pixel_data_format(&self, panel_format: ColorFormat) -> ColorFormat {
+        panel_format
+    }
 }
 
 #[allow(async_fn_in_trait)]
@@ -192,7 +232,7 @@ pub trait BusHardwareFill: DisplayBus {
 /// - Checking status registers.
 /// - Reading back frame memory (e.g., for screenshots), though this is less common.
 ///
-/// Not all physical interfaces support bi-directional communication (e.g., SPI TFT is often 
+/// Not all physical interfaces support bi-directional communication (e.g., SPI TFT is often
 /// write-only).
 pub trait BusRead: DisplayBus {
     /// Reads data from the display.
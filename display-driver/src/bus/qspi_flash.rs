@@ -1,4 +1,4 @@
-use crate::{Area, SolidColor};
+use crate::{Area, ColorFormat, SolidColor};
 
 use super::{BusHardwareFill, BusRead, DisplayBus, DisplayError, ErrorType, Metadata};
 
@@ -17,6 +17,11 @@ impl<B: DisplayBus> QspiFlashBus<B> {
         Self { inner }
     }
 
+    /// Returns the inner bus.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
     #[inline]
     fn assert_cmd_len(&self, cmd: &[u8]) {
         assert_eq!(
@@ -53,6 +58,8 @@ impl<B: DisplayBus> ErrorType for QspiFlashBus<B> {
 }
 
 impl<B: DisplayBus> DisplayBus for QspiFlashBus<B> {
+    const HARDWARE_BYTE_SWAP: bool = B::HARDWARE_BYTE_SWAP;
+
     async fn write_cmd(&mut self, cmd: &[u8]) -> Result<(), Self::Error> {
         self.assert_cmd_len(cmd);
         let cmd = self.to_cmd_and_addr_command(cmd[0]);
@@ -83,6 +90,10 @@ impl<B: DisplayBus> DisplayBus for QspiFlashBus<B> {
     fn set_reset(&mut self, reset: bool) -> Result<(), DisplayError<Self::Error>> {
         self.inner.set_reset(reset)
     }
+
+    fn pixel_data_format(&self, panel_format: ColorFormat) -> ColorFormat {
+        self.inner.pixel_data_format(panel_format)
+    }
 }
 
 impl<B: DisplayBus + BusHardwareFill> BusHardwareFill for QspiFlashBus<B> {
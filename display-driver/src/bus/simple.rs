@@ -6,7 +6,7 @@ use super::*;
 ///
 /// This trait abstracts over simple serial interfaces where commands and data are just streams of
 /// bytes. It provides a convenient way to implement the full [`DisplayBus`] trait without worrying
-/// about frame metadata or pixel-specific handling, as those are handled by the blanket 
+/// about frame metadata or pixel-specific handling, as those are handled by the blanket
 /// implementation.
 ///
 /// Implementors only need to define how to send raw command bytes and raw data bytes.
@@ -34,7 +34,7 @@ pub trait SimpleDisplayBus: ErrorType {
     /// Reset the screen via the bus (optional).
     ///
     /// Note: This method should only be implemented if the hardware has a physical Reset pin.
-    /// Avoid adding a Pin field to your `DisplayBus` wrapper for this purpose; use `LCDResetOption` 
+    /// Avoid adding a Pin field to your `DisplayBus` wrapper for this purpose; use `LCDResetOption`
     /// instead.
     fn set_reset(&mut self, reset: bool) -> Result<(), DisplayError<Self::Error>> {
         let _ = reset;
@@ -69,11 +69,16 @@ impl<T: SimpleDisplayBus> DisplayBus for T {
         &mut self,
         cmd: &[u8],
         data: &[u8],
-        _metadata: Metadata,
+        metadata: Metadata,
     ) -> Result<(), DisplayError<Self::Error>> {
-        T::write_cmd_with_params(self, cmd, data)
-            .await
-            .map_err(DisplayError::BusError)
+        // A continuation of the previous stream must not re-send the memory write command, which
+        // would reset the write pointer to the start of the window.
+        if metadata.area.is_none() {
+            T::write_data(self, data).await
+        } else {
+            T::write_cmd_with_params(self, cmd, data).await
+        }
+        .map_err(DisplayError::BusError)
     }
 
     fn set_reset(&mut self, reset: bool) -> Result<(), DisplayError<Self::Error>> {
@@ -0,0 +1,379 @@
+//! Dithering for reducing the color depth without visible banding.
+//!
+//! A [`Ditherer`] quantizes 8-bit RGB colors to the depth of an RGB [`ColorFormat`], e.g. RGB565
+//! or RGB444, spreading the quantization error spatially:
+//! - [`DitherMethod::Bayer4x4`] and [`DitherMethod::Bayer8x8`] add a position dependent threshold
+//!   (ordered dithering). They are stateless, so pixels can be drawn in any order.
+//! - [`DitherMethod::ErrorDiffusion`] distributes the error to the neighbouring pixels
+//!   (Floyd-Steinberg), using a single row of error values. Pixels must arrive in raster order.
+//!
+//! It is used by [`DitherTarget`] to dither embedded-graphics drawing, and by
+//! [`ColorConvertBus`](crate::bus::ColorConvertBus) to dither while streaming.
+
+use super::ColorFormat;
+
+/// The dithering method of a [`Ditherer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DitherMethod {
+    /// No dithering, colors are rounded to the nearest level.
+    #[default]
+    None,
+    /// Ordered dithering with a 4x4 Bayer matrix.
+    Bayer4x4,
+    /// Ordered dithering with an 8x8 Bayer matrix, smoother but with a larger pattern.
+    Bayer8x8,
+    /// Floyd-Steinberg error diffusion. Needs pixels in raster order.
+    ErrorDiffusion,
+}
+
+/// 4x4 Bayer threshold matrix.
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// 8x8 Bayer threshold matrix, built from the 4x4 one.
+const BAYER_8X8: [[u8; 8]; 8] = {
+    let mut m = [[0u8; 8]; 8];
+    let mut y = 0;
+    while y < 8 {
+        let mut x = 0;
+        while x < 8 {
+            let quadrant = [[0, 2], [3, 1]][y / 4][x / 4];
+            m[y][x] = 4 * BAYER_4X4[y % 4][x % 4] + quadrant;
+            x += 1;
+        }
+        y += 1;
+    }
+    m
+};
+
+/// Quantizes 8-bit RGB colors to an RGB [`ColorFormat`] with dithering.
+///
+/// `W` is the number of columns the error diffusion buffer covers, typically the display width.
+/// It may be 0 if error diffusion isn't used; pixels beyond `W` get no error from the row above.
+#[derive(Debug, Clone)]
+pub struct Ditherer<const W: usize> {
+    method: DitherMethod,
+    /// Bits per channel of the target format.
+    depth: [u8; 3],
+    /// Error carried to the pixels of the next row, per column.
+    errors: [[i16; 3]; W],
+    /// Error carried to the right neighbour.
+    carry: [i16; 3],
+    /// Error for the bottom-right neighbour, added when the column below is written.
+    diagonal: [i16; 3],
+    /// Position of the last pixel, to detect breaks in the raster order.
+    last: Option<(u16, u16)>,
+}
+
+impl<const W: usize> Ditherer<W> {
+    /// Creates a ditherer quantizing to `target`.
+    ///
+    /// Returns `None` if `target` isn't an RGB format, or for [`DitherMethod::ErrorDiffusion`] if
+    /// `W` is 0.
+    pub fn new(method: DitherMethod, target: ColorFormat) -> Option<Self> {
+        if method == DitherMethod::ErrorDiffusion && W == 0 {
+            return None;
+        }
+        let depth = match target {
+            ColorFormat::RGB332 => [3, 3, 2],
+            ColorFormat::RGB444 => [4, 4, 4],
+            ColorFormat::RGB565 => [5, 6, 5],
+            ColorFormat::RGB666 => [6, 6, 6],
+            ColorFormat::RGB888 => [8, 8, 8],
+            _ => return None,
+        };
+        Some(Self {
+            method,
+            depth,
+            errors: [[0; 3]; W],
+            carry: [0; 3],
+            diagonal: [0; 3],
+            last: None,
+        })
+    }
+
+    /// Returns the dithering method.
+    pub fn method(&self) -> DitherMethod {
+        self.method
+    }
+
+    /// Clears the diffused error, e.g. before a new frame.
+    pub fn reset(&mut self) {
+        self.errors = [[0; 3]; W];
+        self.carry = [0; 3];
+        self.diagonal = [0; 3];
+        self.last = None;
+    }
+
+    /// Returns whether `rgb` is exactly representable in the target format, so dithering has no
+    /// effect on it.
+    pub fn is_exact(&self, rgb: [u8; 3]) -> bool {
+        (0..3).all(|c| expand(quantize(rgb[c] as i16, self.depth[c]), self.depth[c]) == rgb[c])
+    }
+
+    /// Dithers the pixel at `(x, y)`.
+    ///
+    /// Returns the quantized color as 8-bit components, with the low bits replicated like
+    /// [`ColorFormat::decode_rgb`] does, so it can be encoded in the target format or converted
+    /// with embedded-graphics without changing it.
+    pub fn dither(&mut self, x: u16, y: u16, rgb: [u8; 3]) -> [u8; 3] {
+        let mut out = [0u8; 3];
+        match self.method {
+            DitherMethod::None => {
+                for c in 0..3 {
+                    out[c] = expand(quantize(rgb[c] as i16, self.depth[c]), self.depth[c]);
+                }
+            }
+            DitherMethod::Bayer4x4 => {
+                let t = BAYER_4X4[y as usize % 4][x as usize % 4];
+                self.ordered(rgb, t, 16, &mut out);
+            }
+            DitherMethod::Bayer8x8 => {
+                let t = BAYER_8X8[y as usize % 8][x as usize % 8];
+                self.ordered(rgb, t, 64, &mut out);
+            }
+            DitherMethod::ErrorDiffusion => self.diffuse(x, y, rgb, &mut out),
+        }
+        out
+    }
+
+    /// Ordered dithering with threshold `t` of `n` levels.
+    fn ordered(&self, rgb: [u8; 3], t: u8, n: i32, out: &mut [u8; 3]) {
+        for c in 0..3 {
+            let max = (1i32 << self.depth[c]) - 1;
+            // Offset in (-0.5, 0.5) quantization steps
+            let offset = (2 * t as i32 + 1 - n) * 255 / (2 * n * max);
+            let v = rgb[c] as i16 + offset as i16;
+            out[c] = expand(quantize(v, self.depth[c]), self.depth[c]);
+        }
+    }
+
+    /// Floyd-Steinberg error diffusion.
+    fn diffuse(&mut self, x: u16, y: u16, rgb: [u8; 3], out: &mut [u8; 3]) {
+        let col = x as usize;
+        if self.last != Some((x.wrapping_sub(1), y)) {
+            self.carry = [0; 3];
+            self.diagonal = [0; 3];
+        }
+        self.last = Some((x, y));
+
+        for c in 0..3 {
+            let below = self.errors.get(col).map_or(0, |e| e[c]);
+            let v = rgb[c] as i16 + below + self.carry[c];
+            let q = expand(quantize(v, self.depth[c]), self.depth[c]);
+            out[c] = q;
+
+            let err = v.clamp(-255, 510) - q as i16;
+            self.carry[c] = err * 7 / 16;
+            if col > 0 {
+                if let Some(e) = self.errors.get_mut(col - 1) {
+                    e[c] += err * 3 / 16;
+                }
+            }
+            if let Some(e) = self.errors.get_mut(col) {
+                e[c] = err * 5 / 16 + self.diagonal[c];
+            }
+            self.diagonal[c] = err / 16;
+        }
+    }
+}
+
+/// Rounds an 8-bit channel value (which may be out of range) to a `depth` bit level.
+fn quantize(v: i16, depth: u8) -> u8 {
+    let max = (1i32 << depth) - 1;
+    let v = (v as i32).clamp(0, 255);
+    ((v * max + 127) / 255) as u8
+}
+
+/// Expands a `depth` bit level to 8 bits by replicating the high bits.
+fn expand(q: u8, depth: u8) -> u8 {
+    if depth >= 8 {
+        return q;
+    }
+    let mut v = (q as u16) << (8 - depth);
+    let mut shift = depth;
+    while shift < 8 {
+        v |= v >> shift;
+        shift *= 2;
+    }
+    v as u8
+}
+
+#[cfg(feature = "embedded-graphics")]
+mod eg_impls {
+    use super::*;
+    use embedded_graphics_core::{
+        draw_target::DrawTarget,
+        geometry::{Dimensions, Point},
+        pixelcolor::{Rgb888, RgbColor},
+        primitives::{PointsIter, Rectangle},
+        Pixel,
+    };
+
+    /// A [`DrawTarget`] adapter that accepts [`Rgb888`] colors and dithers them to the color type
+    /// of the wrapped target, e.g. `Rgb565`.
+    ///
+    /// Solid fills of colors the target can represent exactly are forwarded unchanged; other
+    /// fills are dithered pixel by pixel. With [`DitherMethod::ErrorDiffusion`], draw the content
+    /// in raster order (e.g. with `fill_contiguous` or images) for the best result.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let ditherer = Ditherer::<0>::new(DitherMethod::Bayer4x4, ColorFormat::RGB565).unwrap();
+    /// let mut target = DitherTarget::new(&mut fb_display, ditherer);
+    /// gradient.draw(&mut target).unwrap();
+    /// ```
+    pub struct DitherTarget<'a, D, const W: usize> {
+        target: &'a mut D,
+        ditherer: Ditherer<W>,
+    }
+
+    impl<'a, D, const W: usize> DitherTarget<'a, D, W>
+    where
+        D: DrawTarget,
+        D::Color: From<Rgb888>,
+    {
+        /// Wraps `target`, dithering with `ditherer`.
+        pub fn new(target: &'a mut D, ditherer: Ditherer<W>) -> Self {
+            Self { target, ditherer }
+        }
+
+        /// Returns the ditherer, e.g. to reset it before a new frame.
+        pub fn ditherer_mut(&mut self) -> &mut Ditherer<W> {
+            &mut self.ditherer
+        }
+
+        fn dither(ditherer: &mut Ditherer<W>, point: Point, color: Rgb888) -> D::Color {
+            let [r, g, b] = ditherer.dither(
+                point.x as u16,
+                point.y as u16,
+                [color.r(), color.g(), color.b()],
+            );
+            Rgb888::new(r, g, b).into()
+        }
+    }
+
+    impl<D: DrawTarget, const W: usize> Dimensions for DitherTarget<'_, D, W> {
+        fn bounding_box(&self) -> Rectangle {
+            self.target.bounding_box()
+        }
+    }
+
+    impl<D, const W: usize> DrawTarget for DitherTarget<'_, D, W>
+    where
+        D: DrawTarget,
+        D::Color: From<Rgb888>,
+    {
+        type Color = Rgb888;
+        type Error = D::Error;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            let ditherer = &mut self.ditherer;
+            self.target.draw_iter(
+                pixels
+                    .into_iter()
+                    .map(|Pixel(point, color)| Pixel(point, Self::dither(ditherer, point, color))),
+            )
+        }
+
+        fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Self::Color>,
+        {
+            let ditherer = &mut self.ditherer;
+            let colors = area
+                .points()
+                .zip(colors)
+                .map(|(point, color)| Self::dither(ditherer, point, color));
+            self.target.fill_contiguous(area, colors)
+        }
+
+        fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+            if self.ditherer.is_exact([color.r(), color.g(), color.b()]) {
+                return self.target.fill_solid(area, color.into());
+            }
+            self.fill_contiguous(area, core::iter::repeat(color))
+        }
+    }
+}
+
+#[cfg(feature = "embedded-graphics")]
+pub use eg_impls::DitherTarget;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METHODS: [DitherMethod; 4] = [
+        DitherMethod::None,
+        DitherMethod::Bayer4x4,
+        DitherMethod::Bayer8x8,
+        DitherMethod::ErrorDiffusion,
+    ];
+
+    /// Returns the mean red level of a `size` x `size` block of `gray`.
+    fn mean(ditherer: &mut Ditherer<8>, size: u16, gray: u8) -> u32 {
+        let mut sum = 0;
+        for y in 0..size {
+            for x in 0..size {
+                sum += ditherer.dither(x, y, [gray; 3])[0] as u32;
+            }
+        }
+        sum / (size as u32 * size as u32)
+    }
+
+    #[test]
+    fn new_requires_rgb_target() {
+        assert!(Ditherer::<8>::new(DitherMethod::Bayer4x4, ColorFormat::Gray8).is_none());
+        assert!(Ditherer::<0>::new(DitherMethod::ErrorDiffusion, ColorFormat::RGB565).is_none());
+        assert!(Ditherer::<0>::new(DitherMethod::Bayer8x8, ColorFormat::RGB565).is_some());
+    }
+
+    #[test]
+    fn exact_colors_are_kept() {
+        let exact = [[0; 3], [0xFF; 3], [0x84, 0x82, 0x84]];
+        for method in METHODS {
+            let mut ditherer = Ditherer::<8>::new(method, ColorFormat::RGB565).unwrap();
+            for (x, rgb) in exact.into_iter().enumerate() {
+                assert!(ditherer.is_exact(rgb));
+                assert_eq!(ditherer.dither(x as u16, 0, rgb), rgb, "{method:?}");
+            }
+        }
+        let ditherer = Ditherer::<8>::new(DitherMethod::None, ColorFormat::RGB565).unwrap();
+        assert!(!ditherer.is_exact([0x80; 3]));
+    }
+
+    #[test]
+    fn dithering_preserves_the_mean() {
+        // 0x80 lies between the RGB444 levels 0x77 and 0x88
+        let mut none = Ditherer::<8>::new(DitherMethod::None, ColorFormat::RGB444).unwrap();
+        assert_eq!(mean(&mut none, 8, 0x80), 0x88);
+        for method in [
+            DitherMethod::Bayer4x4,
+            DitherMethod::Bayer8x8,
+            DitherMethod::ErrorDiffusion,
+        ] {
+            let mut ditherer = Ditherer::<8>::new(method, ColorFormat::RGB444).unwrap();
+            let mean = mean(&mut ditherer, 8, 0x80);
+            assert!(mean.abs_diff(0x80) <= 2, "{method:?}: {mean:#x}");
+        }
+
+        // Ordered dithering only uses the two neighbouring levels
+        let mut ditherer = Ditherer::<8>::new(DitherMethod::Bayer4x4, ColorFormat::RGB444).unwrap();
+        let levels: [u8; 4] = core::array::from_fn(|x| ditherer.dither(x as u16, 0, [0x80; 3])[0]);
+        assert!(levels.iter().all(|level| [0x77, 0x88].contains(level)));
+        assert!(levels.contains(&0x77) && levels.contains(&0x88));
+    }
+
+    #[test]
+    fn expand_replicates_high_bits() {
+        assert_eq!(expand(0x1F, 5), 0xFF);
+        assert_eq!(expand(0x10, 5), 0x84);
+        assert_eq!(expand(0b101, 3), 0b1011_0110);
+        assert_eq!(expand(1, 1), 0xFF);
+        assert_eq!(quantize(-20, 5), 0);
+        assert_eq!(quantize(300, 5), 0x1F);
+    }
+}
@@ -0,0 +1,413 @@
+pub mod dither;
+
+#[cfg(feature = "embedded-graphics")]
+pub use dither::DitherTarget;
+pub use dither::{DitherMethod, Ditherer};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Color format used by the display.
+pub enum ColorFormat {
+    /// 1-bit per pixel (Monochrome).
+    Binary,
+    /// 2-bit grayscale.
+    Gray2,
+    /// 4-bit grayscale.
+    Gray4,
+    /// 8-bit grayscale.
+    Gray8,
+    /// 8-bit RGB332.
+    RGB332,
+    /// 12-bit RGB444, two pixels are packed into three bytes.
+    RGB444,
+    /// 16-bit RGB565.
+    RGB565,
+    /// 18-bit RGB666.
+    RGB666,
+    /// 24-bit RGB888.
+    RGB888,
+}
+
+impl ColorFormat {
+    /// Returns the number of bits per pixel for this format.
+    pub const fn size_bits(self) -> u8 {
+        match self {
+            ColorFormat::Binary => 1,
+            ColorFormat::Gray2 => 2,
+            ColorFormat::Gray4 => 4,
+            ColorFormat::Gray8 => 8,
+            ColorFormat::RGB332 => 8,
+            ColorFormat::RGB444 => 12,
+            ColorFormat::RGB565 => 16,
+            ColorFormat::RGB666 => 18,
+            ColorFormat::RGB888 => 24,
+        }
+    }
+
+    /// Returns the number of bytes of a single pixel, rounded up for the packed formats.
+    pub const fn size_bytes(self) -> u8 {
+        match self {
+            ColorFormat::Binary => 1,
+            ColorFormat::Gray2 => 1,
+            ColorFormat::Gray4 => 1,
+            ColorFormat::Gray8 => 1,
+            ColorFormat::RGB332 => 1,
+            ColorFormat::RGB444 => 2,
+            ColorFormat::RGB565 => 2,
+            ColorFormat::RGB666 => 3,
+            ColorFormat::RGB888 => 3,
+        }
+    }
+
+    /// Returns the number of bits a pixel occupies in the pixel data.
+    ///
+    /// This is [`size_bits`](Self::size_bits), except for RGB666, which is sent in three bytes.
+    pub const fn wire_bits(self) -> u8 {
+        match self {
+            ColorFormat::RGB666 => 24,
+            _ => self.size_bits(),
+        }
+    }
+
+    /// Returns whether pixels of this format are packed, i.e. don't start at a byte boundary.
+    pub const fn is_packed(self) -> bool {
+        !self.wire_bits().is_multiple_of(8)
+    }
+
+    /// Returns the length in bytes and pixels of the shortest run of pixels that ends at a byte
+    /// boundary, e.g. 3 bytes for 2 pixels of RGB444 or 1 byte for 8 pixels of Binary.
+    pub const fn pattern_size(self) -> (usize, usize) {
+        let bits = self.wire_bits() as usize;
+        let gcd = match bits % 8 {
+            0 => 8,
+            4 => 4,
+            2 | 6 => 2,
+            _ => 1,
+        };
+        (bits / gcd, 8 / gcd)
+    }
+
+    /// Returns the number of bytes of `pixels` pixels, including the padding of a final partial
+    /// byte.
+    pub const fn bytes_for(self, pixels: usize) -> usize {
+        (pixels * self.wire_bits() as usize).div_ceil(8)
+    }
+
+    /// Returns whether this is one of the RGB formats.
+    pub const fn is_rgb(self) -> bool {
+        matches!(
+            self,
+            ColorFormat::RGB332
+                | ColorFormat::RGB444
+                | ColorFormat::RGB565
+                | ColorFormat::RGB666
+                | ColorFormat::RGB888
+        )
+    }
+
+    /// Decodes one pixel of an RGB format into 8-bit components, replicating the high bits so that
+    /// full intensity stays full intensity.
+    ///
+    /// `bytes` must hold at least [`size_bytes`](Self::size_bytes) bytes, in the wire layout:
+    /// RGB565 big-endian, RGB666 with left-aligned components (`RRRRRR00`), and RGB444 as a single
+    /// left-aligned pixel (`RRRRGGGG BBBB0000`). Returns `None` for non-RGB formats.
+    pub fn decode_rgb(self, bytes: &[u8]) -> Option<[u8; 3]> {
+        match self {
+            ColorFormat::RGB332 => {
+                let v = bytes[0];
+                let (r, g, b) = (v >> 5, v >> 2 & 0x07, v & 0x03);
+                let c3 = |c: u8| c << 5 | c << 2 | c >> 1;
+                Some([c3(r), c3(g), b * 0x55])
+            }
+            ColorFormat::RGB444 => {
+                let (r, g, b) = (bytes[0] >> 4, bytes[0] & 0x0F, bytes[1] >> 4);
+                Some([r * 0x11, g * 0x11, b * 0x11])
+            }
+            ColorFormat::RGB565 => {
+                let v = u16::from_be_bytes([bytes[0], bytes[1]]);
+                let (r, g, b) = ((v >> 11) as u8, (v >> 5) as u8 & 0x3F, v as u8 & 0x1F);
+                Some([r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2])
+            }
+            ColorFormat::RGB666 => {
+                let c = |v: u8| (v & 0xFC) | v >> 6;
+                Some([c(bytes[0]), c(bytes[1]), c(bytes[2])])
+            }
+            ColorFormat::RGB888 => Some([bytes[0], bytes[1], bytes[2]]),
+            _ => None,
+        }
+    }
+
+    /// Encodes 8-bit components into one pixel of an RGB format, truncating the low bits.
+    ///
+    /// Writes [`size_bytes`](Self::size_bytes) bytes to `out` in the layout described in
+    /// [`decode_rgb`](Self::decode_rgb). Returns `None` for non-RGB formats.
+    pub fn encode_rgb(self, rgb: [u8; 3], out: &mut [u8]) -> Option<()> {
+        let [r, g, b] = rgb;
+        match self {
+            ColorFormat::RGB332 => out[0] = (r & 0xE0) | (g >> 5) << 2 | b >> 6,
+            ColorFormat::RGB444 => out[..2].copy_from_slice(&[(r & 0xF0) | g >> 4, b & 0xF0]),
+            ColorFormat::RGB565 => {
+                let v = (r as u16 >> 3) << 11 | (g as u16 >> 2) << 5 | b as u16 >> 3;
+                out[..2].copy_from_slice(&v.to_be_bytes());
+            }
+            ColorFormat::RGB666 => out[..3].copy_from_slice(&[r & 0xFC, g & 0xFC, b & 0xFC]),
+            ColorFormat::RGB888 => out[..3].copy_from_slice(&rgb),
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorType {
+    Gray(u8),
+    Rgb(u8, u8, u8),
+}
+
+/// A single color, ready to be sent to the display.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SolidColor {
+    /// The pixel bytes, in the wire layout of [`ColorFormat::decode_rgb`]. Pixels of packed and
+    /// grayscale formats are left-aligned in the first byte(s), e.g. `G1` is `0b1000_0000`.
+    pub raw: [u8; 3],
+    pub format: ColorFormat,
+    /// The components, in the depth of the format.
+    pub color: ColorType,
+}
+
+impl SolidColor {
+    /// Creates a color of an RGB format from 8-bit components.
+    ///
+    /// Returns `None` for non-RGB formats.
+    pub fn from_rgb888(format: ColorFormat, rgb: [u8; 3]) -> Option<Self> {
+        let mut raw = [0u8; 3];
+        format.encode_rgb(rgb, &mut raw)?;
+        let [r, g, b] = rgb;
+        let color = match format {
+            ColorFormat::RGB332 => ColorType::Rgb(r >> 5, g >> 5, b >> 6),
+            ColorFormat::RGB444 => ColorType::Rgb(r >> 4, g >> 4, b >> 4),
+            ColorFormat::RGB565 => ColorType::Rgb(r >> 3, g >> 2, b >> 3),
+            ColorFormat::RGB666 => ColorType::Rgb(r >> 2, g >> 2, b >> 2),
+            _ => ColorType::Rgb(r, g, b),
+        };
+        Some(Self { raw, format, color })
+    }
+
+    /// Creates a color of a grayscale format (including [`ColorFormat::Binary`]) from a luma value
+    /// in the depth of the format.
+    ///
+    /// Returns `None` for RGB formats, or if `luma` doesn't fit the format.
+    pub fn from_luma(format: ColorFormat, luma: u8) -> Option<Self> {
+        let bits = match format {
+            ColorFormat::Binary | ColorFormat::Gray2 | ColorFormat::Gray4 | ColorFormat::Gray8 => {
+                format.size_bits()
+            }
+            _ => return None,
+        };
+        if bits < 8 && luma >> bits != 0 {
+            return None;
+        }
+        Some(Self {
+            raw: [luma << (8 - bits), 0, 0],
+            format,
+            color: ColorType::Gray(luma),
+        })
+    }
+
+    /// Returns this color in `format`, converting between RGB formats.
+    ///
+    /// Returns `None` if the formats differ and either is not an RGB format.
+    pub fn to_format(&self, format: ColorFormat) -> Option<Self> {
+        if self.format == format {
+            return Some(self.clone());
+        }
+        if !format.is_rgb() {
+            return None;
+        }
+        Self::from_rgb888(format, self.format.decode_rgb(&self.raw)?)
+    }
+
+    /// Returns the shortest byte pattern that repeats this color, with its length in bytes and
+    /// pixels, e.g. 3 bytes for 2 pixels of RGB444 or 1 byte for 8 pixels of Binary.
+    pub fn pattern(&self) -> ([u8; 3], usize, usize) {
+        let bits = self.format.wire_bits() as usize;
+        let (bytes, pixels) = self.format.pattern_size();
+
+        let mut pattern = [0u8; 3];
+        for i in 0..pixels {
+            pack_pixel(&mut pattern, i * bits, &self.raw, bits);
+        }
+        (pattern, bytes, pixels)
+    }
+}
+
+/// Writes the `bits` left-aligned bits of `raw` to `buffer`, starting at bit `bit_offset` (MSB
+/// first).
+///
+/// The other bits of `buffer` are kept, so packed pixels can be written one after another.
+pub(crate) fn pack_pixel(buffer: &mut [u8], bit_offset: usize, raw: &[u8], bits: usize) {
+    if bit_offset.is_multiple_of(8) && bits.is_multiple_of(8) {
+        let start = bit_offset / 8;
+        buffer[start..start + bits / 8].copy_from_slice(&raw[..bits / 8]);
+        return;
+    }
+    for i in 0..bits {
+        let bit = raw[i / 8] >> (7 - i % 8) & 1;
+        let pos = bit_offset + i;
+        let mask = 0x80 >> (pos % 8);
+        buffer[pos / 8] = (buffer[pos / 8] & !mask) | if bit != 0 { mask } else { 0 };
+    }
+}
+
+#[cfg(feature = "embedded-graphics")]
+mod eg_impls {
+    use super::*;
+    use embedded_graphics_core::pixelcolor::{
+        raw::ToBytes, BinaryColor, Gray2, Gray4, Gray8, GrayColor, PixelColor, Rgb565, Rgb666,
+        Rgb888, RgbColor,
+    };
+
+    impl From<BinaryColor> for SolidColor {
+        fn from(value: BinaryColor) -> Self {
+            SolidColor {
+                raw: [if value.is_on() { 0x80 } else { 0 }, 0, 0],
+                format: ColorFormat::Binary,
+                color: ColorType::Gray(value.is_on() as u8),
+            }
+        }
+    }
+
+    impl From<Gray2> for SolidColor {
+        fn from(value: Gray2) -> Self {
+            SolidColor {
+                raw: [value.luma() << 6, 0, 0],
+                format: ColorFormat::Gray2,
+                color: ColorType::Gray(value.luma()),
+            }
+        }
+    }
+
+    impl From<Gray4> for SolidColor {
+        fn from(value: Gray4) -> Self {
+            SolidColor {
+                raw: [value.luma() << 4, 0, 0],
+                format: ColorFormat::Gray4,
+                color: ColorType::Gray(value.luma()),
+            }
+        }
+    }
+
+    impl From<Gray8> for SolidColor {
+        fn from(value: Gray8) -> Self {
+            SolidColor {
+                raw: [value.luma(), 0, 0],
+                format: ColorFormat::Gray8,
+                color: ColorType::Gray(value.luma()),
+            }
+        }
+    }
+
+    impl From<Rgb565> for SolidColor {
+        fn from(value: Rgb565) -> Self {
+            let mut raw = [0u8; 3];
+            raw[0..2].copy_from_slice(&<Rgb565 as PixelColor>::Raw::from(value).to_be_bytes());
+            SolidColor {
+                raw,
+                format: ColorFormat::RGB565,
+                color: ColorType::Rgb(value.r(), value.g(), value.b()),
+            }
+        }
+    }
+
+    #[cfg(feature = "embedded-graphics")]
+    impl From<Rgb666> for SolidColor {
+        fn from(value: Rgb666) -> Self {
+            let raw = [
+                (value.r() & 0x3F) << 2,
+                (value.g() & 0x3F) << 2,
+                (value.b() & 0x3F) << 2,
+            ];
+            SolidColor {
+                raw,
+                format: ColorFormat::RGB666,
+                color: ColorType::Rgb(value.r(), value.g(), value.b()),
+            }
+        }
+    }
+
+    #[cfg(feature = "embedded-graphics")]
+    impl From<Rgb888> for SolidColor {
+        fn from(value: Rgb888) -> Self {
+            let mut raw = [0u8; 3];
+            raw.copy_from_slice(&<Rgb888 as PixelColor>::Raw::from(value).to_be_bytes());
+            SolidColor {
+                raw,
+                format: ColorFormat::RGB888,
+                color: ColorType::Rgb(value.r(), value.g(), value.b()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_size() {
+        let sizes = [
+            ColorFormat::Binary,
+            ColorFormat::Gray2,
+            ColorFormat::Gray4,
+            ColorFormat::Gray8,
+            ColorFormat::RGB332,
+            ColorFormat::RGB444,
+            ColorFormat::RGB565,
+            ColorFormat::RGB666,
+            ColorFormat::RGB888,
+        ]
+        .map(ColorFormat::pattern_size);
+        assert_eq!(
+            sizes,
+            [
+                (1, 8),
+                (1, 4),
+                (1, 2),
+                (1, 1),
+                (1, 1),
+                (3, 2),
+                (2, 1),
+                (3, 1),
+                (3, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn pack_pixel_packs_msb_first() {
+        // Three RGB444 pixels
+        let mut buffer = [0u8; 5];
+        for (i, raw) in [[0x12, 0x30], [0x45, 0x60], [0x78, 0x90]]
+            .iter()
+            .enumerate()
+        {
+            pack_pixel(&mut buffer, i * 12, raw, 12);
+        }
+        assert_eq!(buffer, [0x12, 0x34, 0x56, 0x78, 0x90]);
+
+        // Gray2 pixels keep the bits around them
+        let mut buffer = [0b1111_1111];
+        pack_pixel(&mut buffer, 2, &[0b0100_0000], 2);
+        assert_eq!(buffer, [0b1101_1111]);
+
+        let mut buffer = [0u8; 4];
+        pack_pixel(&mut buffer, 8, &[0xAB, 0xCD], 16);
+        assert_eq!(buffer, [0, 0xAB, 0xCD, 0]);
+    }
+
+    #[test]
+    fn solid_color_pattern() {
+        let color = SolidColor::from_rgb888(ColorFormat::RGB444, [0x10, 0x20, 0x30]).unwrap();
+        let (pattern, bytes, pixels) = color.pattern();
+        assert_eq!((&pattern[..bytes], pixels), (&[0x12, 0x31, 0x23][..], 2));
+    }
+}
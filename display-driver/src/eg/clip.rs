@@ -0,0 +1,136 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
+
+use crate::panel::shape::VisibleShape;
+
+/// A [`DrawTarget`] wrapper that skips pixels outside a [`VisibleShape`].
+///
+/// The shape is evaluated in screen coordinates, so `offset` is the screen position of the wrapped
+/// target's origin, e.g. the area of a partial framebuffer.
+///
+/// # Example
+/// ```ignore
+/// let mut fb_display = FrameBufferedDisplayDriver::new(driver, &mut framebuffer).unwrap();
+///
+/// // Only the pixels inside the round glass are drawn
+/// fb_display.clipped().clear(Rgb565::BLUE).unwrap();
+/// fb_display.flush_visible(1).await.unwrap();
+/// ```
+pub struct ShapeClip<'a, D> {
+    target: &'a mut D,
+    clip: Clip,
+}
+
+impl<'a, D: DrawTarget> ShapeClip<'a, D> {
+    /// Wraps `target`, clipping to `shape` on a screen of `screen_size` (width, height).
+    pub fn new(
+        target: &'a mut D,
+        shape: VisibleShape,
+        screen_size: (u16, u16),
+        offset: Point,
+    ) -> Self {
+        Self {
+            target,
+            clip: Clip {
+                shape,
+                screen_size,
+                offset,
+            },
+        }
+    }
+}
+
+/// The clipping parameters, kept apart from the target so they can be used while it is borrowed.
+#[derive(Clone, Copy)]
+struct Clip {
+    shape: VisibleShape,
+    screen_size: (u16, u16),
+    offset: Point,
+}
+
+impl Clip {
+    /// Returns the visible columns of target row `y` as a target-relative `(x0, x1)` range.
+    fn span(&self, y: i32) -> Option<(i32, i32)> {
+        let screen_y = y + self.offset.y;
+        if screen_y < 0 || screen_y > u16::MAX as i32 {
+            return None;
+        }
+        let (w, h) = self.screen_size;
+        let (x0, x1) = self.shape.span(screen_y as u16, w, h)?;
+        Some((x0 as i32 - self.offset.x, x1 as i32 - self.offset.x))
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        matches!(self.span(point.y), Some((x0, x1)) if point.x >= x0 && point.x <= x1)
+    }
+}
+
+impl<D: DrawTarget> Dimensions for ShapeClip<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for ShapeClip<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        if self.clip.shape == VisibleShape::Rectangle {
+            return self.target.draw_iter(pixels);
+        }
+        let clip = self.clip;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(point, _)| clip.contains(*point)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if self.clip.shape == VisibleShape::Rectangle {
+            return self.target.fill_contiguous(area, colors);
+        }
+        let pixels = area
+            .points()
+            .zip(colors)
+            .map(|(point, color)| Pixel(point, color));
+        self.draw_iter(pixels)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        if self.clip.shape == VisibleShape::Rectangle {
+            return self.target.fill_solid(area, color);
+        }
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+        for y in area.top_left.y..=bottom_right.y {
+            let Some((x0, x1)) = self.clip.span(y) else {
+                continue;
+            };
+            let x0 = x0.max(area.top_left.x);
+            let x1 = x1.min(bottom_right.x);
+            if x0 <= x1 {
+                let row = Rectangle::new(Point::new(x0, y), Size::new((x1 - x0 + 1) as u32, 1));
+                self.target.fill_solid(&row, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.bounding_box();
+        self.fill_solid(&area, color)
+    }
+}
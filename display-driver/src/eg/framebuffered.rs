@@ -1,17 +1,46 @@
 use crate::{
-    bus::DisplayBus,
-    color::ColorFormat,
-    panel::{Orientation, Panel, PanelSetBrightness},
-    Area, DisplayDriver, DisplayError, FrameControl,
+    bus::{DisplayBus, Metadata},
+    color::{self, ColorFormat},
+    eg::{palette, Palette, ShapeClip},
+    panel::{
+        backlight::{Backlight, BrightnessControl},
+        power::{LCDEnableOption, NoPin},
+        shape::VisibleShape,
+        Mirror, Orientation, Panel,
+    },
+    Area, DisplayDriver, DisplayError, FrameControl, PixelByteOrder,
 };
 use delegate::delegate;
 use embedded_graphics::framebuffer::Framebuffer;
 use embedded_graphics_core::{
-    draw_target::DrawTarget, geometry::OriginDimensions, geometry::Size, pixelcolor::PixelColor,
+    draw_target::DrawTarget,
+    geometry::OriginDimensions,
+    geometry::Point,
+    geometry::Size,
+    pixelcolor::raw::{BigEndian, LittleEndian, RawData},
+    pixelcolor::PixelColor,
     Pixel,
 };
+use embedded_hal::digital::OutputPin;
 use embedded_hal_async::delay::DelayNs;
 
+/// Size of the buffer used to rotate pixels in software while flushing.
+const ROTATION_CHUNK_BYTES: usize = 256;
+
+/// Maps the byte order of an embedded-graphics [`Framebuffer`] to a [`PixelByteOrder`].
+pub trait FramebufferByteOrder {
+    /// The byte order of the framebuffer data.
+    const PIXEL_BYTE_ORDER: PixelByteOrder;
+}
+
+impl FramebufferByteOrder for BigEndian {
+    const PIXEL_BYTE_ORDER: PixelByteOrder = PixelByteOrder::BigEndian;
+}
+
+impl FramebufferByteOrder for LittleEndian {
+    const PIXEL_BYTE_ORDER: PixelByteOrder = PixelByteOrder::LittleEndian;
+}
+
 /// A display driver that buffers drawing operations in a framebuffer.
 ///
 /// This struct wraps an underlying [`DisplayDriver`] and a mutable reference to an
@@ -31,7 +60,7 @@ use embedded_hal_async::delay::DelayNs;
 ///
 /// ```ignore
 /// // Initialize driver and framebuffer
-/// let mut fb_display = FrameBufferedDisplayDriver::new(driver, &mut framebuffer);
+/// let mut fb_display = FrameBufferedDisplayDriver::new(driver, &mut framebuffer)?;
 ///
 /// // Draw elements onto the framebuffer
 /// fb_display.clear(BinaryColor::Off).unwrap();
@@ -55,6 +84,29 @@ use embedded_hal_async::delay::DelayNs;
 /// // Flush to the specified sub-region of the screen
 /// fb_display.flush().await.unwrap();
 /// ```
+///
+/// ## Byte Order
+///
+/// Both `BigEndian` and `LittleEndian` framebuffers are supported. For 16-bit colors in a
+/// `LittleEndian` framebuffer (e.g. native RGB565 from a software renderer), the constructors set
+/// the driver's [`PixelByteOrder`] so the bytes are swapped during transfer. The constructors
+/// return [`DisplayError::Unsupported`] for little-endian colors wider than 16 bits.
+///
+/// ## Software Rotation
+///
+/// Changing the panel orientation with [`set_orientation`](Self::set_orientation) swaps the
+/// screen's width and height, which a fixed `W` x `H` framebuffer can't follow. Instead, keep the
+/// panel orientation and set a software rotation with [`set_rotation`](Self::set_rotation): the
+/// framebuffer then holds the content in the rotated (logical) orientation, and flushing
+/// transposes and mirrors it in software, chunk by chunk. This also works for panels or buses
+/// that can't rotate in hardware, e.g. RGB interface streaming.
+///
+/// ```ignore
+/// // A 240x320 portrait panel, drawn in landscape
+/// let mut fb_display = FrameBufferedDisplayDriver::new(driver, &mut framebuffer_320x240)?;
+/// fb_display.set_rotation(Orientation::Deg90)?;
+/// fb_display.flush().await.unwrap();
+/// ```
 pub struct FrameBufferedDisplayDriver<
     'a,
     B,
@@ -65,40 +117,76 @@ pub struct FrameBufferedDisplayDriver<
     const W: usize,
     const H: usize,
     const N: usize,
+    PWR = NoPin,
+    BL = LCDEnableOption<NoPin>,
 > where
     B: DisplayBus,
     P: Panel<B>,
     C: PixelColor<Raw = R>,
+    PWR: OutputPin,
+    BL: Backlight,
 {
-    pub driver: DisplayDriver<B, P>,
+    pub driver: DisplayDriver<B, P, PWR, BL>,
     area: Area,
+    rotation: Orientation,
     framebuffer: &'a mut Framebuffer<C, R, BO, W, H, N>,
 }
 
-impl<'a, B, P, C, R, BO, const W: usize, const H: usize, const N: usize>
-    FrameBufferedDisplayDriver<'a, B, P, C, R, BO, W, H, N>
+/// Iterates over the framebuffer coordinates of the pixels in `window`, in the raster order of
+/// the panel, for a `w` x `h` framebuffer shown rotated by `rotation`. `window` is relative to
+/// the framebuffer's area on the panel.
+fn rotated_points(
+    rotation: Orientation,
+    w: usize,
+    h: usize,
+    window: Area,
+) -> impl Iterator<Item = (usize, usize)> {
+    let (x0, y0) = (window.x as usize, window.y as usize);
+    let (x1, y1) = (x0 + window.w as usize, y0 + window.h as usize);
+    (y0..y1).flat_map(move |py| {
+        (x0..x1).map(move |px| match rotation {
+            Orientation::Deg0 => (px, py),
+            Orientation::Deg90 => (py, h - 1 - px),
+            Orientation::Deg180 => (w - 1 - px, h - 1 - py),
+            Orientation::Deg270 => (w - 1 - py, px),
+        })
+    })
+}
+
+impl<'a, B, P, C, R, BO, const W: usize, const H: usize, const N: usize, PWR, BL>
+    FrameBufferedDisplayDriver<'a, B, P, C, R, BO, W, H, N, PWR, BL>
 where
     B: DisplayBus,
     P: Panel<B>,
     C: PixelColor<Raw = R>,
+    PWR: OutputPin,
+    BL: Backlight,
+    R: RawData,
+    BO: FramebufferByteOrder,
 {
     /// Creates a new `FrameBufferedDisplayDriver`.
     ///
     /// By default, the display area is set to the full size of the framebuffer (`W` x `H`),
     /// starting at the origin (0, 0).
     ///
+    /// # Errors
+    /// Returns `DisplayError::Unsupported` for a `LittleEndian` framebuffer with colors wider than
+    /// 16 bits.
+    ///
     /// # Arguments
     /// * `driver` - The underlying display driver.
     /// * `framebuffer` - A mutable reference to the framebuffer of size `W` x `H`.
     pub fn new(
-        driver: DisplayDriver<B, P>,
+        mut driver: DisplayDriver<B, P, PWR, BL>,
         framebuffer: &'a mut Framebuffer<C, R, BO, W, H, N>,
-    ) -> Self {
-        Self {
+    ) -> Result<Self, DisplayError<B::Error>> {
+        driver.set_pixel_byte_order(Self::pixel_byte_order()?);
+        Ok(Self {
             driver,
             area: Area::from_origin(W as u16, H as u16),
+            rotation: Orientation::Deg0,
             framebuffer,
-        }
+        })
     }
 
     /// Creates a new `FrameBufferedDisplayDriver` with a specific partial display area.
@@ -107,41 +195,154 @@ where
     ///
     /// # Errors
     /// Returns `DisplayError::InvalidArgs` if the size of the `area` does not exactly match
-    /// the dimensions of the framebuffer (`W` x `H`).
+    /// the dimensions of the framebuffer (`W` x `H`), and `DisplayError::Unsupported` for a
+    /// `LittleEndian` framebuffer with colors wider than 16 bits.
     ///
     /// # Arguments
     /// * `driver` - The underlying display driver.
     /// * `area` - The sub-region on the display screen where the framebuffer should be drawn.
     /// * `framebuffer` - A mutable reference to the framebuffer.
     pub fn new_partial(
-        driver: DisplayDriver<B, P>,
+        mut driver: DisplayDriver<B, P, PWR, BL>,
         area: Area,
         framebuffer: &'a mut Framebuffer<C, R, BO, W, H, N>,
     ) -> Result<Self, DisplayError<B::Error>> {
         if area.w as usize != W || area.h as usize != H {
             return Err(DisplayError::InvalidArgs);
         }
+        driver.set_pixel_byte_order(Self::pixel_byte_order()?);
 
         Ok(Self {
             driver,
             area,
+            rotation: Orientation::Deg0,
             framebuffer,
         })
     }
 
+    /// Returns the byte order to transfer the framebuffer with; swapping only applies to 16-bit
+    /// colors, and wider little-endian colors are unsupported.
+    fn pixel_byte_order() -> Result<PixelByteOrder, DisplayError<B::Error>> {
+        match (BO::PIXEL_BYTE_ORDER, R::BITS_PER_PIXEL) {
+            (PixelByteOrder::LittleEndian, 17..) => Err(DisplayError::Unsupported),
+            (byte_order, 16) => Ok(byte_order),
+            _ => Ok(PixelByteOrder::BigEndian),
+        }
+    }
+
     /// Updates the display area where the framebuffer will be drawn.
     ///
+    /// With a software rotation, the area is in rotated coordinates.
+    ///
     /// # Errors
     /// Returns `DisplayError::InvalidArgs` if the new area does not exactly match
-    /// the dimensions of the framebuffer.
+    /// the dimensions of the framebuffer, or doesn't fit the rotated screen.
     pub fn set_area(&mut self, area: Area) -> Result<(), DisplayError<B::Error>> {
-        if area.w as usize != W || area.h as usize != H {
+        if area.w as usize != W || area.h as usize != H || !self.fits_rotated(area, self.rotation) {
             return Err(DisplayError::InvalidArgs);
         }
         self.area = area;
         Ok(())
     }
 
+    /// Sets the software rotation: the framebuffer content is shown rotated clockwise by
+    /// `rotation`, relative to the panel's orientation.
+    ///
+    /// The display area is then in rotated coordinates, i.e. on a screen of the panel's size with
+    /// width and height swapped for 90° and 270°. The panel orientation should be left unchanged
+    /// while a rotation is set.
+    ///
+    /// # Errors
+    /// Returns `DisplayError::InvalidArgs` if the display area doesn't fit the rotated screen.
+    pub fn set_rotation(&mut self, rotation: Orientation) -> Result<(), DisplayError<B::Error>> {
+        if !self.fits_rotated(self.area, rotation) {
+            return Err(DisplayError::InvalidArgs);
+        }
+        self.rotation = rotation;
+        Ok(())
+    }
+
+    /// Gets the software rotation.
+    pub fn rotation(&self) -> Orientation {
+        self.rotation
+    }
+
+    /// Returns whether `area` fits the screen rotated by `rotation`. Without rotation, the area
+    /// isn't checked, as the panel may be larger than it reports.
+    fn fits_rotated(&self, area: Area, rotation: Orientation) -> bool {
+        let (w, h) = self.driver.panel.size();
+        match rotation {
+            Orientation::Deg0 => true,
+            Orientation::Deg180 => area.is_within(&Area::from_origin(w, h)),
+            Orientation::Deg90 | Orientation::Deg270 => area.is_within(&Area::from_origin(h, w)),
+        }
+    }
+
+    /// Maps `area` from rotated coordinates to panel coordinates.
+    fn to_panel(&self, area: Area) -> Area {
+        let (sw, sh) = self.driver.panel.size();
+        let Area { x, y, w, h } = area;
+        match self.rotation {
+            Orientation::Deg0 => area,
+            Orientation::Deg90 => Area::new(sw.saturating_sub(y + h), x, h, w),
+            Orientation::Deg180 => {
+                Area::new(sw.saturating_sub(x + w), sh.saturating_sub(y + h), w, h)
+            }
+            Orientation::Deg270 => Area::new(y, sh.saturating_sub(x + w), h, w),
+        }
+    }
+
+    /// Writes `window` (in panel coordinates, within the framebuffer's area) from the rotated
+    /// framebuffer, copying the pixels in panel order into a chunk buffer.
+    async fn write_rotated(
+        &mut self,
+        window: Area,
+        frame_control: FrameControl,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let data = self.framebuffer.data();
+        if data.len() % (W * H) != 0 {
+            return Err(DisplayError::Unsupported);
+        }
+        let pixel_size = data.len() / (W * H);
+        if pixel_size == 0 {
+            return Err(DisplayError::Unsupported);
+        }
+        let stride = W * pixel_size;
+
+        let origin = self.to_panel(self.area);
+        let local = Area::new(window.x - origin.x, window.y - origin.y, window.w, window.h);
+        let mut points = rotated_points(self.rotation, W, H, local).peekable();
+
+        self.driver.set_window(window).await?;
+        let mut buffer = [0u8; ROTATION_CHUNK_BYTES];
+        let mut metadata = Metadata::new_from_parts(
+            Some(window),
+            FrameControl {
+                first: frame_control.first,
+                last: false,
+            },
+        );
+        loop {
+            let mut len = 0;
+            while len + pixel_size <= ROTATION_CHUNK_BYTES {
+                let Some((x, y)) = points.next() else {
+                    break;
+                };
+                let start = y * stride + x * pixel_size;
+                buffer[len..len + pixel_size].copy_from_slice(&data[start..start + pixel_size]);
+                len += pixel_size;
+            }
+            let done = points.peek().is_none();
+            metadata.frame_control.last = frame_control.last && done;
+
+            self.driver.send_pixels(&buffer[..len], metadata).await?;
+            if done {
+                return Ok(());
+            }
+            metadata = Metadata::new_continue_stream();
+        }
+    }
+
     /// Gets the current display area.
     pub fn area(&self) -> Area {
         self.area
@@ -176,13 +377,230 @@ where
         &mut self,
         frame_control: FrameControl,
     ) -> Result<(), DisplayError<B::Error>> {
+        if self.rotation != Orientation::Deg0 {
+            let window = self.to_panel(self.area);
+            return self.write_rotated(window, frame_control).await;
+        }
         self.driver
             .write_pixels(self.area, frame_control, self.framebuffer.data())
             .await
     }
 
+    /// Flushes the part of the framebuffer covering `area`, in screen coordinates.
+    ///
+    /// The area is expanded to the panel's alignment, taking the extra pixels from the
+    /// framebuffer, so any dirty rectangle can be flushed. See
+    /// [`DisplayDriver::write_pixels_from`] for the errors. With a software rotation, `area` is in
+    /// rotated coordinates and is aligned after rotating it.
+    pub async fn flush_area(&mut self, area: Area) -> Result<(), DisplayError<B::Error>> {
+        self.flush_area_with_frame_control(area, FrameControl::new_standalone())
+            .await
+    }
+
+    /// Like [`flush_area`](Self::flush_area), using custom frame control settings.
+    pub async fn flush_area_with_frame_control(
+        &mut self,
+        area: Area,
+        frame_control: FrameControl,
+    ) -> Result<(), DisplayError<B::Error>> {
+        if self.rotation != Orientation::Deg0 {
+            if area.w == 0 || area.h == 0 || !area.is_within(&self.area) {
+                return Err(DisplayError::InvalidArgs);
+            }
+            let window = self.driver.align_area(self.to_panel(area));
+            if !window.is_within(&self.to_panel(self.area)) {
+                return Err(DisplayError::UnalignedArea);
+            }
+            return self.write_rotated(window, frame_control).await;
+        }
+        self.driver
+            .write_pixels_from(area, frame_control, self.framebuffer.data(), self.area)
+            .await
+    }
+
+    /// Flushes an indexed-color framebuffer, expanding each index through `palette` into the
+    /// palette's color format.
+    ///
+    /// The framebuffer color (e.g. `Gray8` or `Gray4`) holds palette indices, with indices beyond
+    /// the palette mapped to entry 0. The pixels are expanded into a `CHUNK` byte buffer, chunk
+    /// by chunk, so no full-color framebuffer is needed. Changing the palette between flushes
+    /// recolors the whole frame without redrawing it, see [`Palette`]. The software rotation is
+    /// applied.
+    ///
+    /// # Errors
+    /// Returns [`DisplayError::Unsupported`] for framebuffer colors wider than 8 bits or an empty
+    /// palette, and [`DisplayError::InvalidArgs`] if the palette's format differs from the
+    /// driver's [`color_format`](DisplayDriver::color_format) or `CHUNK` is too small for one
+    /// pixel.
+    pub async fn flush_palette<const CHUNK: usize, const COLORS: usize>(
+        &mut self,
+        palette: &Palette<COLORS>,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let bits = R::BITS_PER_PIXEL;
+        if bits > 8 || COLORS == 0 {
+            return Err(DisplayError::Unsupported);
+        }
+        let format = palette.format();
+        if self
+            .driver
+            .color_format()
+            .is_some_and(|active| active != format)
+        {
+            return Err(DisplayError::InvalidArgs);
+        }
+        let color_bits = format.wire_bits() as usize;
+        let (pattern_bytes, pattern_pixels) = format.pattern_size();
+        let pixels_per_chunk = CHUNK / pattern_bytes * pattern_pixels;
+        if pixels_per_chunk == 0 {
+            return Err(DisplayError::InvalidArgs);
+        }
+
+        let stride = (W * bits).div_ceil(8);
+        let window = self.to_panel(self.area);
+        let local = Area::from_origin(window.w, window.h);
+        self.driver.set_window(window).await?;
+
+        let data = self.framebuffer.data();
+        let mut indices = rotated_points(self.rotation, W, H, local)
+            .map(|point| palette::index_at(data, stride, bits, point))
+            .peekable();
+        let mut buffer = [0u8; CHUNK];
+        let mut metadata = Metadata::new_from_parts(Some(window), FrameControl::new_first());
+        while indices.peek().is_some() {
+            let mut pixels = 0;
+            for index in indices.by_ref().take(pixels_per_chunk) {
+                let raw = &palette.lookup(index).raw;
+                color::pack_pixel(&mut buffer, pixels * color_bits, raw, color_bits);
+                pixels += 1;
+            }
+            metadata.frame_control.last = indices.peek().is_none();
+
+            let len = format.bytes_for(pixels);
+            self.driver.send_pixels(&buffer[..len], metadata).await?;
+            metadata = Metadata::new_continue_stream();
+        }
+        Ok(())
+    }
+
+    /// Flushes only the visible pixels of the framebuffer, according to the panel's
+    /// [`VisibleShape`].
+    ///
+    /// Each group of `rows_per_window` rows is sent as its own window, covering only the visible
+    /// span of those rows. On a circular panel this saves about 21% of the bandwidth with one row
+    /// per window; larger groups need fewer window commands but send more invisible pixels.
+    /// Windows are widened to the panel's alignment.
+    ///
+    /// Falls back to [`flush`](Self::flush) for rectangular panels and with a software rotation.
+    /// Returns
+    /// [`DisplayError::Unsupported`] for color formats with less than one byte per pixel.
+    pub async fn flush_visible(
+        &mut self,
+        rows_per_window: u16,
+    ) -> Result<(), DisplayError<B::Error>> {
+        self.flush_visible_with_frame_control(rows_per_window, FrameControl::new_standalone())
+            .await
+    }
+
+    /// Like [`flush_visible`](Self::flush_visible), using custom frame control settings.
+    ///
+    /// `frame_control.first` applies to the first window and `frame_control.last` to the last one.
+    pub async fn flush_visible_with_frame_control(
+        &mut self,
+        rows_per_window: u16,
+        frame_control: FrameControl,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let shape = self.driver.panel.visible_shape();
+        if shape == VisibleShape::Rectangle || self.rotation != Orientation::Deg0 {
+            return self.flush_with_frame_control(frame_control).await;
+        }
+
+        let data = self.framebuffer.data();
+        if data.len() % (W * H) != 0 {
+            return Err(DisplayError::Unsupported);
+        }
+        let stride = data.len() / H;
+        let pixel_size = stride / W;
+
+        let (screen_w, screen_h) = self.driver.panel.size();
+        let (x_align, y_align) = (
+            self.driver.panel.x_alignment(),
+            self.driver.panel.y_alignment(),
+        );
+        let rows_per_window = rows_per_window.max(1).next_multiple_of(y_align);
+        let area = self.area;
+
+        // The visible window of the rows `y..y + h`, relative to the framebuffer.
+        let window = |y: u16, h: u16| -> Option<Area> {
+            let (mut x0, mut x1) = (u16::MAX, 0);
+            for row in y..y + h {
+                if let Some((s0, s1)) = shape.span(area.y + row, screen_w, screen_h) {
+                    let s0 = s0.max(area.x);
+                    let s1 = s1.min(area.x + area.w - 1);
+                    if s0 <= s1 {
+                        x0 = x0.min(s0);
+                        x1 = x1.max(s1);
+                    }
+                }
+            }
+            if x0 > x1 {
+                return None;
+            }
+            let x0 = (x0 - x0 % x_align).max(area.x);
+            let x1 = (x1 + 1).next_multiple_of(x_align).min(area.x + area.w);
+            Some(Area::new(x0 - area.x, y, x1 - x0, h))
+        };
+
+        let mut first = frame_control.first;
+        let mut pending: Option<Area> = None;
+        let mut y = 0;
+        loop {
+            let next = if y < area.h {
+                let h = rows_per_window.min(area.h - y);
+                y += h;
+                match window(y - h, h) {
+                    Some(next) => Some(next),
+                    None => continue,
+                }
+            } else {
+                None
+            };
+
+            if let Some(w) = pending {
+                let rows = (w.y..w.y + w.h).map(|row| {
+                    let start = row as usize * stride + w.x as usize * pixel_size;
+                    &data[start..start + w.w as usize * pixel_size]
+                });
+                let fc = FrameControl {
+                    first,
+                    last: next.is_none() && frame_control.last,
+                };
+                let screen_area = Area::new(area.x + w.x, area.y + w.y, w.w, w.h);
+                self.driver.write_pixel_rows(screen_area, fc, rows).await?;
+                first = false;
+            }
+
+            match next {
+                Some(next) => pending = Some(next),
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Returns a [`DrawTarget`] on the framebuffer that skips the pixels outside the panel's
+    /// [`VisibleShape`].
+    pub fn clipped(&mut self) -> ShapeClip<'_, Framebuffer<C, R, BO, W, H, N>>
+    where
+        Framebuffer<C, R, BO, W, H, N>: DrawTarget,
+    {
+        let shape = self.driver.panel.visible_shape();
+        let offset = Point::new(self.area.x as i32, self.area.y as i32);
+        ShapeClip::new(self.framebuffer, shape, self.driver.panel.size(), offset)
+    }
+
     /// Returns the inner DisplayDriver.
-    pub fn into_inner(self) -> DisplayDriver<B, P> {
+    ///
+    /// Its pixel byte order is left as set by the constructor.
+    pub fn into_inner(self) -> DisplayDriver<B, P, PWR, BL> {
         self.driver
     }
 
@@ -190,47 +608,61 @@ where
         to self.driver {
             /// Initializes the display.
             pub async fn init(&mut self, delay: &mut impl DelayNs) -> Result<(), DisplayError<B::Error>>;
+            /// Shuts the display down in the reverse order of initialization.
+            pub async fn shutdown(&mut self, delay: &mut impl DelayNs) -> Result<(), DisplayError<B::Error>>;
+            /// Turns the display on or off, sequencing the backlight accordingly.
+            pub async fn set_display_on(&mut self, on: bool) -> Result<(), DisplayError<B::Error>>;
             /// Sets the pixel color format.
             pub async fn set_color_format(&mut self, color_format: ColorFormat) -> Result<(), DisplayError<B::Error>>;
             /// Sets the display orientation.
             pub async fn set_orientation(&mut self, orientation: Orientation) -> Result<(), DisplayError<B::Error>>;
+            /// Sets the display mirroring.
+            pub async fn set_mirror(&mut self, mirror: Mirror) -> Result<(), DisplayError<B::Error>>;
         }
     }
 }
 
-impl<'a, B, P, C, R, BO, const W: usize, const H: usize, const N: usize>
-    FrameBufferedDisplayDriver<'a, B, P, C, R, BO, W, H, N>
+impl<'a, B, P, C, R, BO, const W: usize, const H: usize, const N: usize, PWR, BL>
+    FrameBufferedDisplayDriver<'a, B, P, C, R, BO, W, H, N, PWR, BL>
 where
     B: DisplayBus,
-    P: Panel<B> + PanelSetBrightness<B>,
+    P: Panel<B>,
     C: PixelColor<Raw = R>,
+    PWR: OutputPin,
+    BL: BrightnessControl<B, P>,
 {
     delegate! {
         to self.driver {
-            /// Sets the display brightness (if supported by the panel).
+            /// Sets the display brightness (0-255).
             pub async fn set_brightness(&mut self, brightness: u8) -> Result<(), DisplayError<B::Error>>;
+            /// Fades from the current brightness to `target` over `duration_ms`.
+            pub async fn fade_brightness(&mut self, target: u8, duration_ms: u32, delay: &mut impl DelayNs) -> Result<(), DisplayError<B::Error>>;
         }
     }
 }
 
-impl<'a, B, P, C, R, BO, const W: usize, const H: usize, const N: usize> OriginDimensions
-    for FrameBufferedDisplayDriver<'a, B, P, C, R, BO, W, H, N>
+impl<'a, B, P, C, R, BO, const W: usize, const H: usize, const N: usize, PWR, BL> OriginDimensions
+    for FrameBufferedDisplayDriver<'a, B, P, C, R, BO, W, H, N, PWR, BL>
 where
     B: DisplayBus,
     P: Panel<B>,
     C: PixelColor<Raw = R>,
+    PWR: OutputPin,
+    BL: Backlight,
 {
     fn size(&self) -> Size {
         self.framebuffer.size()
     }
 }
 
-impl<'a, B, P, C, R, BO, const W: usize, const H: usize, const N: usize> DrawTarget
-    for FrameBufferedDisplayDriver<'a, B, P, C, R, BO, W, H, N>
+impl<'a, B, P, C, R, BO, const W: usize, const H: usize, const N: usize, PWR, BL> DrawTarget
+    for FrameBufferedDisplayDriver<'a, B, P, C, R, BO, W, H, N, PWR, BL>
 where
     B: DisplayBus,
     P: Panel<B>,
     C: PixelColor<Raw = R>,
+    PWR: OutputPin,
+    BL: Backlight,
     Framebuffer<C, R, BO, W, H, N>: DrawTarget<Color = C>,
 {
     type Color = C;
@@ -266,3 +698,37 @@ where
         self.framebuffer.clear(color)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use std::vec::Vec;
+
+    use super::*;
+
+    /// Returns the indices (`y * 3 + x`) of a 3x2 framebuffer in the panel order of `window`.
+    fn indices(rotation: Orientation, window: Area) -> Vec<usize> {
+        rotated_points(rotation, 3, 2, window)
+            .map(|(x, y)| y * 3 + x)
+            .collect()
+    }
+
+    #[test]
+    fn rotated_points_turn_clockwise() {
+        // Framebuffer:
+        // 0 1 2
+        // 3 4 5
+        let landscape = Area::from_origin(3, 2);
+        let portrait = Area::from_origin(2, 3);
+        assert_eq!(indices(Orientation::Deg0, landscape), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(indices(Orientation::Deg90, portrait), [3, 0, 4, 1, 5, 2]);
+        assert_eq!(indices(Orientation::Deg180, landscape), [5, 4, 3, 2, 1, 0]);
+        assert_eq!(indices(Orientation::Deg270, portrait), [2, 5, 1, 4, 0, 3]);
+    }
+
+    #[test]
+    fn rotated_points_of_a_window() {
+        assert_eq!(indices(Orientation::Deg90, Area::new(1, 1, 1, 2)), [1, 2]);
+        assert_eq!(indices(Orientation::Deg180, Area::new(0, 1, 2, 1)), [2, 1]);
+    }
+}
@@ -1,3 +1,9 @@
+pub mod clip;
 pub mod framebuffered;
+pub mod palette;
+pub mod recorder;
 
-pub use framebuffered::FrameBufferedDisplayDriver;
+pub use clip::ShapeClip;
+pub use framebuffered::{FrameBufferedDisplayDriver, FramebufferByteOrder};
+pub use palette::Palette;
+pub use recorder::{DrawRecorder, RecorderFull};
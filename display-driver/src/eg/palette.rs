@@ -0,0 +1,184 @@
+use core::ops::Range;
+
+use crate::{ColorFormat, SolidColor};
+
+/// A color palette for indexed-color framebuffers.
+///
+/// An indexed framebuffer stores a palette index per pixel instead of the color, e.g. 8 bits with
+/// `Gray8` or 4 bits with `Gray4` as index type, which halves or quarters the memory of an RGB565
+/// framebuffer. [`FrameBufferedDisplayDriver::flush_palette`](super::FrameBufferedDisplayDriver::flush_palette)
+/// expands the indices through the palette while streaming.
+///
+/// Changing the palette changes all pixels using the modified entries on the next flush, without
+/// redrawing, which allows cheap color animations such as fades or color cycling.
+///
+/// The expansion is done in software. The DCS `WRITE_LUT` (Command 0x2D) of some controllers maps
+/// color components rather than pixel indices, so it can't be used for this.
+///
+/// The entries are stored in the palette's [`ColorFormat`], which must match the color format of
+/// the panel. Colors of other RGB formats are converted when they are set.
+///
+/// # Example
+/// ```ignore
+/// let mut framebuffer = Framebuffer::<Gray8, _, BigEndian, 240, 320, { buffer_size::<Gray8>(240, 320) }>::new();
+/// let mut palette = Palette::<256>::from_fn(ColorFormat::RGB565, |i| {
+///     Rgb565::new(i as u8 >> 3, i as u8 >> 2, 0).into()
+/// })
+/// .unwrap();
+///
+/// let mut fb_display = FrameBufferedDisplayDriver::new(driver, &mut framebuffer).unwrap();
+/// Circle::new(Point::new(10, 10), 40)
+///     .into_styled(PrimitiveStyle::with_fill(Gray8::new(200)))
+///     .draw(&mut fb_display)
+///     .unwrap();
+///
+/// fb_display.flush_palette::<512, 256>(&palette).await.unwrap();
+///
+/// // Color cycling
+/// palette.rotate(16..32, 1);
+/// fb_display.flush_palette::<512, 256>(&palette).await.unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Palette<const P: usize> {
+    format: ColorFormat,
+    colors: [SolidColor; P],
+}
+
+impl<const P: usize> Palette<P> {
+    /// Creates a palette of `format` with all entries set to `color`.
+    ///
+    /// Returns `None` if `color` can't be converted to `format`, see [`SolidColor::to_format`].
+    pub fn new(format: ColorFormat, color: impl Into<SolidColor>) -> Option<Self> {
+        let color = color.into().to_format(format)?;
+        Some(Self {
+            format,
+            colors: core::array::from_fn(|_| color.clone()),
+        })
+    }
+
+    /// Creates a palette of `format` with entry `i` set to `f(i)`.
+    ///
+    /// Returns `None` if a color can't be converted to `format`.
+    pub fn from_fn(format: ColorFormat, mut f: impl FnMut(usize) -> SolidColor) -> Option<Self> {
+        let mut palette = Self::new(format, f(0))?;
+        for index in 1..P {
+            palette.set(index, f(index))?;
+        }
+        Some(palette)
+    }
+
+    /// Returns the color format of the entries.
+    pub fn format(&self) -> ColorFormat {
+        self.format
+    }
+
+    /// Sets entry `index` to `color`.
+    ///
+    /// Returns `None` if `index` is out of range or `color` can't be converted to the palette's
+    /// format; the entry is left unchanged then.
+    pub fn set(&mut self, index: usize, color: impl Into<SolidColor>) -> Option<()> {
+        let entry = self.colors.get_mut(index)?;
+        *entry = color.into().to_format(self.format)?;
+        Some(())
+    }
+
+    /// Returns entry `index`.
+    pub fn get(&self, index: usize) -> Option<&SolidColor> {
+        self.colors.get(index)
+    }
+
+    /// Rotates the entries in `range` by `by` positions towards the start, for color cycling.
+    pub fn rotate(&mut self, range: Range<usize>, by: usize) {
+        let entries = &mut self.colors[range];
+        if !entries.is_empty() {
+            entries.rotate_left(by % entries.len());
+        }
+    }
+
+    /// Returns the color of `index`, falling back to entry 0 for indices out of range.
+    pub(crate) fn lookup(&self, index: usize) -> &SolidColor {
+        self.colors.get(index).unwrap_or(&self.colors[0])
+    }
+}
+
+/// Returns the palette index of pixel `(x, y)` in framebuffer data with `stride` bytes per row
+/// and `bits` bits per index.
+///
+/// Sub-byte indices are read MSB first, as embedded-graphics packs them whatever the byte order
+/// of the framebuffer.
+pub(crate) fn index_at(data: &[u8], stride: usize, bits: usize, (x, y): (usize, usize)) -> usize {
+    let bit = x * bits;
+    let byte = data[y * stride + bit / 8] as u16;
+    let shift = 8 - bits - bit % 8;
+    ((byte >> shift) & ((1 << bits) - 1)) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{
+        framebuffer::{buffer_size, Framebuffer},
+        pixelcolor::{
+            raw::{LittleEndian, RawU4},
+            Gray4, Rgb565,
+        },
+        prelude::*,
+    };
+
+    extern crate std;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::{
+        eg::FrameBufferedDisplayDriver,
+        test_util::{block_on, MockBus, MockPanel},
+        DisplayDriver,
+    };
+
+    #[test]
+    fn index_at_reads_msb_first() {
+        let data = [0x12, 0x34, 0b1011_0001];
+        let indices: Vec<_> = (0..4).map(|x| index_at(&data, 2, 4, (x, 0))).collect();
+        assert_eq!(indices, [0x1, 0x2, 0x3, 0x4]);
+        let bits: Vec<_> = (0..8).map(|x| index_at(&data, 1, 1, (x, 2))).collect();
+        assert_eq!(bits, [1, 0, 1, 1, 0, 0, 0, 1]);
+        assert_eq!(index_at(&data, 1, 2, (1, 2)), 0b11);
+    }
+
+    #[test]
+    fn palette_entries_are_converted_to_format() {
+        let palette =
+            Palette::<4>::from_fn(ColorFormat::RGB565, |i| Rgb565::new(i as u8, 0, 0).into())
+                .unwrap();
+        assert_eq!(palette.format(), ColorFormat::RGB565);
+        assert_eq!(
+            palette.lookup(3).raw,
+            SolidColor::from(Rgb565::new(3, 0, 0)).raw
+        );
+        // Out of range indices map to entry 0.
+        assert_eq!(palette.lookup(7).raw, palette.lookup(0).raw);
+    }
+
+    #[test]
+    fn flush_little_endian_sub_byte_framebuffer() {
+        const W: usize = 4;
+        let mut framebuffer =
+            Framebuffer::<Gray4, RawU4, LittleEndian, W, 1, { buffer_size::<Gray4>(W, 1) }>::new();
+        for x in 0..W as i32 {
+            Pixel(Point::new(x, 0), Gray4::new(x as u8))
+                .draw(&mut framebuffer)
+                .unwrap();
+        }
+        let palette =
+            Palette::<16>::from_fn(ColorFormat::RGB565, |i| Rgb565::new(i as u8, 0, 0).into())
+                .unwrap();
+
+        let driver = DisplayDriver::new(MockBus::<false>::default(), MockPanel::new(W as u16, 1));
+        let mut display = FrameBufferedDisplayDriver::new(driver, &mut framebuffer).unwrap();
+        block_on(display.flush_palette::<64, 16>(&palette)).unwrap();
+
+        let expected: Vec<u8> = (0..W as u8)
+            .flat_map(|i| Rgb565::new(i, 0, 0).into_storage().to_be_bytes())
+            .collect();
+        assert_eq!(display.driver.bus.pixels(), expected);
+    }
+}
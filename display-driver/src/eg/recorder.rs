@@ -0,0 +1,469 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    pixelcolor::PixelColor,
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    bus::{BusBytesIo, BusHardwareFill, DisplayBus},
+    color::{pack_pixel, ColorFormat},
+    panel::{backlight::Backlight, Panel},
+    Area, DisplayDriver, DisplayError, FrameControl, PixelByteOrder, SolidColor,
+};
+
+/// Error returned by [`DrawRecorder`] when its operation or pixel storage is full.
+///
+/// The operations recorded so far are kept, so they can be replayed before drawing the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecorderFull;
+
+/// A recorded drawing operation.
+#[derive(Clone, Copy)]
+enum DrawOp<C> {
+    /// Fill `area` with `color`.
+    FillSolid { area: Area, color: C },
+    /// Write the pixel bytes `data[start..end]` to `area`.
+    Pixels {
+        area: Area,
+        start: usize,
+        end: usize,
+    },
+}
+
+/// A [`DrawTarget`] that records drawing operations, to be replayed on a [`DisplayDriver`]
+/// without a framebuffer.
+///
+/// `DrawTarget` is synchronous while the driver is async, so drawing happens in two steps: draw
+/// into the recorder, then [`replay`](Self::replay) it with `.await`. This needs far less RAM than
+/// a framebuffer, which makes it suitable for small MCUs:
+/// - `fill_solid` is recorded as a single operation and replayed with
+///   [`DisplayDriver::fill_solid_batch`] or [`DisplayDriver::fill_solid_via_bus`].
+/// - `fill_contiguous` stores the pixel data of the area.
+/// - `draw_iter` batches runs of horizontally adjacent pixels, also across calls. Runs of the same
+///   columns on consecutive rows are merged into one rectangle.
+///
+/// At most `OPS` operations and `BYTES` bytes of pixel data are recorded. Drawing beyond that
+/// returns [`RecorderFull`].
+///
+/// Every operation sets its own window, so on panels with an alignment requirement (e.g. CO5300)
+/// the drawn areas must be aligned, otherwise replaying returns [`DisplayError::UnalignedArea`].
+/// Pixels drawn one by one rarely are, so draw text with a background color there, which is
+/// recorded with `fill_contiguous`.
+///
+/// # Example
+/// ```ignore
+/// let mut recorder = DrawRecorder::<Rgb565, 32, 1024>::new(driver.panel.size());
+///
+/// recorder.clear(Rgb565::BLACK).unwrap();
+/// Text::new("Hello", Point::new(10, 20), style).draw(&mut recorder).unwrap();
+///
+/// recorder.replay::<512, _, _, _, _>(&mut driver).await.unwrap();
+/// recorder.reset();
+/// ```
+pub struct DrawRecorder<C, const OPS: usize, const BYTES: usize> {
+    size: (u16, u16),
+    ops: [Option<DrawOp<C>>; OPS],
+    ops_len: usize,
+    data: [u8; BYTES],
+    /// Length of the pixel data in bits, as packed formats don't end at a byte boundary.
+    data_bits: usize,
+    /// Format of the recorded pixel data.
+    format: Option<ColorFormat>,
+}
+
+impl<C, const OPS: usize, const BYTES: usize> DrawRecorder<C, OPS, BYTES>
+where
+    C: PixelColor + Into<SolidColor>,
+{
+    /// Creates an empty recorder for a screen of `size` (width, height).
+    pub fn new(size: (u16, u16)) -> Self {
+        Self {
+            size,
+            ops: [None; OPS],
+            ops_len: 0,
+            data: [0; BYTES],
+            data_bits: 0,
+            format: None,
+        }
+    }
+
+    /// Returns the number of recorded operations.
+    pub fn len(&self) -> usize {
+        self.ops_len
+    }
+
+    /// Returns whether no operation is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.ops_len == 0
+    }
+
+    /// Discards all recorded operations.
+    pub fn reset(&mut self) {
+        self.ops_len = 0;
+        self.data_bits = 0;
+    }
+
+    /// Replays the recorded operations on `driver`, filling solid areas with
+    /// [`DisplayDriver::fill_solid_batch`] using an `N` byte buffer.
+    ///
+    /// Returns [`DisplayError::InvalidArgs`] without drawing anything if the recorded colors
+    /// differ from the driver's [`color_format`](DisplayDriver::color_format).
+    pub async fn replay<const N: usize, B, P, PWR, BL>(
+        &self,
+        driver: &mut DisplayDriver<B, P, PWR, BL>,
+    ) -> Result<(), DisplayError<B::Error>>
+    where
+        B: DisplayBus + BusBytesIo,
+        P: Panel<B>,
+        PWR: OutputPin,
+        BL: Backlight,
+    {
+        self.check_format(driver.color_format())?;
+        for op in self.ops() {
+            match op {
+                DrawOp::FillSolid { area, color } => {
+                    driver.fill_solid_batch::<N>(color.into(), area).await?
+                }
+                DrawOp::Pixels { area, start, end } => {
+                    self.write_pixels(driver, area, start, end).await?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replays the recorded operations on `driver`, filling solid areas with
+    /// [`DisplayDriver::fill_solid_via_bus`].
+    ///
+    /// Returns [`DisplayError::InvalidArgs`] like [`replay`](Self::replay).
+    pub async fn replay_via_bus<B, P, PWR, BL>(
+        &self,
+        driver: &mut DisplayDriver<B, P, PWR, BL>,
+    ) -> Result<(), DisplayError<B::Error>>
+    where
+        B: DisplayBus + BusHardwareFill,
+        P: Panel<B>,
+        PWR: OutputPin,
+        BL: Backlight,
+    {
+        self.check_format(driver.color_format())?;
+        for op in self.ops() {
+            match op {
+                DrawOp::FillSolid { area, color } => {
+                    driver.fill_solid_via_bus(color.into(), area).await?
+                }
+                DrawOp::Pixels { area, start, end } => {
+                    self.write_pixels(driver, area, start, end).await?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn ops(&self) -> impl Iterator<Item = DrawOp<C>> + '_ {
+        self.ops[..self.ops_len].iter().flatten().copied()
+    }
+
+    /// Checks that the recorded colors can be sent as they are in the `active` format.
+    fn check_format<E>(&self, active: Option<ColorFormat>) -> Result<(), DisplayError<E>> {
+        let Some(active) = active else {
+            return Ok(());
+        };
+        let matches = self.ops().all(|op| match op {
+            DrawOp::FillSolid { color, .. } => color.into().format == active,
+            DrawOp::Pixels { .. } => self.format == Some(active),
+        });
+        if matches {
+            Ok(())
+        } else {
+            Err(DisplayError::InvalidArgs)
+        }
+    }
+
+    async fn write_pixels<B, P, PWR, BL>(
+        &self,
+        driver: &mut DisplayDriver<B, P, PWR, BL>,
+        area: Area,
+        start: usize,
+        end: usize,
+    ) -> Result<(), DisplayError<B::Error>>
+    where
+        B: DisplayBus,
+        P: Panel<B>,
+        PWR: OutputPin,
+        BL: Backlight,
+    {
+        // The recorded colors are big-endian, whatever the driver is set up for
+        let byte_order = driver.pixel_byte_order();
+        driver.set_pixel_byte_order(PixelByteOrder::BigEndian);
+        let result = driver
+            .write_pixels(area, FrameControl::new_standalone(), &self.data[start..end])
+            .await;
+        driver.set_pixel_byte_order(byte_order);
+        result
+    }
+
+    fn push_op(&mut self, op: DrawOp<C>) -> Result<(), RecorderFull> {
+        self.merge_last_row();
+        let slot = self.ops.get_mut(self.ops_len).ok_or(RecorderFull)?;
+        *slot = Some(op);
+        self.ops_len += 1;
+        Ok(())
+    }
+
+    /// Merges the last operation into the one before if it is the next row of the same columns,
+    /// with the pixel data following without padding.
+    fn merge_last_row(&mut self) {
+        let pixel_bits = self.pixel_bits();
+        let [.., Some(prev), Some(last)] = &mut self.ops[..self.ops_len] else {
+            return;
+        };
+        let (
+            DrawOp::Pixels {
+                area: prev_area,
+                end: prev_end,
+                ..
+            },
+            DrawOp::Pixels { area, start, end },
+        ) = (prev, *last)
+        else {
+            return;
+        };
+        if area.h == 1
+            && area.x == prev_area.x
+            && area.w == prev_area.w
+            && area.y == prev_area.y + prev_area.h
+            && start == *prev_end
+            && (area.w as usize * pixel_bits).is_multiple_of(8)
+        {
+            prev_area.h += 1;
+            *prev_end = end;
+            self.ops_len -= 1;
+        }
+    }
+
+    /// Returns whether `point` is the next pixel of the last operation, a single row whose pixel
+    /// data is the last recorded.
+    fn continues_last_row(&self, point: Point) -> bool {
+        match self.ops[..self.ops_len].last() {
+            Some(Some(DrawOp::Pixels { area, end, .. })) => {
+                area.h == 1
+                    && *end == self.data_end()
+                    && point == Point::new(area.x as i32 + area.w as i32, area.y as i32)
+            }
+            _ => false,
+        }
+    }
+
+    /// Adds the pixel just recorded to the last operation, see
+    /// [`continues_last_row`](Self::continues_last_row).
+    fn grow_last_row(&mut self) {
+        let data_end = self.data_end();
+        if let Some(Some(DrawOp::Pixels { area, end, .. })) = self.ops[..self.ops_len].last_mut() {
+            area.w += 1;
+            *end = data_end;
+        }
+    }
+
+    /// Starts the pixel data of a new operation at a byte boundary, returning its start.
+    fn start_data(&mut self) -> usize {
+        self.data_bits = self.data_bits.next_multiple_of(8);
+        self.data_bits / 8
+    }
+
+    /// Returns the bits per pixel of the recorded pixel data.
+    fn pixel_bits(&self) -> usize {
+        self.format.map_or(0, |format| format.wire_bits() as usize)
+    }
+
+    /// Returns the end of the pixel data, including the padding of a final partial byte.
+    fn data_end(&self) -> usize {
+        self.data_bits.div_ceil(8)
+    }
+
+    fn push_color(&mut self, color: C) -> Result<(), RecorderFull> {
+        let color: SolidColor = color.into();
+        let bits = color.format.wire_bits() as usize;
+        if (self.data_bits + bits).div_ceil(8) > BYTES {
+            return Err(RecorderFull);
+        }
+        pack_pixel(&mut self.data, self.data_bits, &color.raw, bits);
+        self.data_bits += bits;
+        self.format = Some(color.format);
+        Ok(())
+    }
+}
+
+impl<C, const OPS: usize, const BYTES: usize> OriginDimensions for DrawRecorder<C, OPS, BYTES> {
+    fn size(&self) -> Size {
+        Size::new(self.size.0 as u32, self.size.1 as u32)
+    }
+}
+
+impl<C, const OPS: usize, const BYTES: usize> DrawTarget for DrawRecorder<C, OPS, BYTES>
+where
+    C: PixelColor + Into<SolidColor>,
+{
+    type Color = C;
+    type Error = RecorderFull;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.bounding_box();
+        for Pixel(point, color) in pixels {
+            if !bounds.contains(point) {
+                continue;
+            }
+            if self.continues_last_row(point) {
+                self.push_color(color)?;
+                self.grow_last_row();
+                continue;
+            }
+
+            // Check the capacity first, so a failed pixel leaves no empty operation behind
+            self.merge_last_row();
+            if self.ops_len == OPS {
+                return Err(RecorderFull);
+            }
+            let start = self.start_data();
+            self.push_color(color)?;
+            self.push_op(DrawOp::Pixels {
+                area: Area::new(point.x as u16, point.y as u16, 1, 1),
+                start,
+                end: self.data_end(),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let visible = area.intersection(&self.bounding_box());
+        if visible != *area {
+            let pixels = area
+                .points()
+                .zip(colors)
+                .map(|(point, color)| Pixel(point, color));
+            return self.draw_iter(pixels);
+        }
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        // Drop the pixel data of the area again if it doesn't fit
+        let data_bits = self.data_bits;
+        let start = self.start_data();
+        let pixels = area.size.width as usize * area.size.height as usize;
+        let result = colors
+            .into_iter()
+            .take(pixels)
+            .try_for_each(|color| self.push_color(color))
+            .and_then(|()| {
+                self.push_op(DrawOp::Pixels {
+                    area: (*area).into(),
+                    start,
+                    end: self.data_end(),
+                })
+            });
+        if result.is_err() {
+            self.data_bits = data_bits;
+        }
+        result
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+        self.push_op(DrawOp::FillSolid {
+            area: area.into(),
+            color,
+        })
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        // Everything recorded before is overdrawn
+        self.reset();
+        self.fill_solid(&self.bounding_box(), color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{
+        pixelcolor::{Rgb565, RgbColor},
+        prelude::*,
+    };
+
+    extern crate std;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::test_util::{block_on, MockBus, MockPanel};
+
+    fn rgb565_bytes(colors: &[Rgb565]) -> Vec<u8> {
+        colors
+            .iter()
+            .flat_map(|color| color.into_storage().to_be_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn full_fill_contiguous_leaves_no_data_behind() {
+        let mut recorder = DrawRecorder::<Rgb565, 4, 8>::new((8, 8));
+        let row = [Rgb565::RED, Rgb565::GREEN, Rgb565::BLUE];
+        recorder
+            .fill_contiguous(&Rectangle::new(Point::zero(), Size::new(3, 1)), row)
+            .unwrap();
+        assert_eq!(
+            recorder.fill_contiguous(
+                &Rectangle::new(Point::new(0, 1), Size::new(2, 1)),
+                [Rgb565::WHITE; 2]
+            ),
+            Err(RecorderFull)
+        );
+        // The pixel data of the failed fill is dropped, so one more pixel fits
+        Pixel(Point::new(5, 5), Rgb565::WHITE)
+            .draw(&mut recorder)
+            .unwrap();
+
+        let mut driver = DisplayDriver::new(MockBus::<false>::default(), MockPanel::new(8, 8));
+        block_on(recorder.replay::<16, _, _, _, _>(&mut driver)).unwrap();
+        let writes: Vec<_> = driver
+            .bus
+            .pixel_writes
+            .iter()
+            .map(|write| write.data.clone())
+            .collect();
+        assert_eq!(writes, [rgb565_bytes(&row), rgb565_bytes(&[Rgb565::WHITE])]);
+    }
+
+    #[test]
+    fn replay_rejects_other_color_format() {
+        let mut recorder = DrawRecorder::<Rgb565, 4, 8>::new((8, 8));
+        recorder.clear(Rgb565::BLACK).unwrap();
+        Pixel(Point::new(1, 1), Rgb565::WHITE)
+            .draw(&mut recorder)
+            .unwrap();
+
+        let mut driver = DisplayDriver::new(MockBus::<false>::default(), MockPanel::new(8, 8));
+        block_on(driver.set_color_format(ColorFormat::RGB888)).unwrap();
+        assert!(matches!(
+            block_on(recorder.replay::<16, _, _, _, _>(&mut driver)),
+            Err(DisplayError::InvalidArgs)
+        ));
+        assert!(driver.bus.pixel_writes.is_empty() && driver.bus.data.is_empty());
+
+        block_on(driver.set_color_format(ColorFormat::RGB565)).unwrap();
+        block_on(recorder.replay::<16, _, _, _, _>(&mut driver)).unwrap();
+        assert_eq!(driver.bus.pixels(), rgb565_bytes(&[Rgb565::WHITE]));
+    }
+}
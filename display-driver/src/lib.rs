@@ -5,17 +5,32 @@ pub mod bus;
 pub mod color;
 pub mod panel;
 
+#[cfg(test)]
+mod test_util;
+
 #[cfg(feature = "embedded-graphics")]
 pub mod eg;
 
 pub use crate::area::Area;
 pub use crate::bus::{
-    BusBytesIo, BusHardwareFill, DisplayBus, FrameControl, Metadata, SimpleDisplayBus,
+    BusBytesIo, BusHardwareFill, DisplayBus, FrameControl, Metadata, PixelByteOrder,
+    SimpleDisplayBus,
 };
 pub use color::{ColorFormat, ColorType, SolidColor};
-pub use panel::{reset::LCDResetOption, Orientation, Panel, PanelSetBrightness};
+pub use panel::{
+    backlight::PwmBacklight,
+    power::{LCDEnableOption, NoPin},
+    reset::LCDResetOption,
+    shape::VisibleShape,
+    Mirror, Orientation, Panel, PanelSetBrightness,
+};
 
+use embedded_hal::digital::OutputPin;
 use embedded_hal_async::delay::DelayNs;
+use panel::backlight::{Backlight, BrightnessControl, FadeSteps, FADE_STEP_MS};
+
+/// Size of the buffer used to swap the bytes of little-endian pixel data in software.
+const SWAP_CHUNK_BYTES: usize = 256;
 
 /// Error type for display operations.
 #[derive(Debug)]
@@ -30,6 +45,15 @@ pub enum DisplayError<E> {
     InvalidArgs,
     /// The area is unaligned.
     UnalignedArea,
+    /// Error propagated from a GPIO pin (e.g. the reset pin), reduced to its generic kind.
+    PinError(embedded_hal::digital::ErrorKind),
+    /// Error propagated from a PWM channel (e.g. the backlight), reduced to its generic kind.
+    PwmError(embedded_hal::pwm::ErrorKind),
+    /// Data read back from the display does not match what was written.
+    VerifyFailed,
+    /// The selected reset method is not supported (e.g. [`LCDResetOption::Bus`] on a bus without
+    /// a reset line).
+    ResetUnsupported,
 }
 
 impl<E> From<E> for DisplayError<E> {
@@ -48,13 +72,28 @@ impl<E> From<E> for DisplayError<E> {
 /// let mut display = DisplayDriver::builder(bus, panel)
 ///     .with_color_format(ColorFormat::RGB565)
 ///     .with_orientation(Orientation::Deg270)
+///     .with_power_pin(LCDEnableOption::new_pin(lcd_pwr))
+///     .with_backlight_pin(LCDEnableOption::new_pin(lcd_bl))
 ///     .init(&mut delay).await.unwrap();
 /// ```
-pub struct DisplayDriverBuilder<B: DisplayBus, P: Panel<B>> {
+pub struct DisplayDriverBuilder<B, P, PWR = NoPin, BL = LCDEnableOption<NoPin>, C = ChunkedClear>
+where
+    B: DisplayBus,
+    P: Panel<B>,
+    PWR: OutputPin,
+    BL: Backlight,
+{
     bus: B,
     panel: P,
     color_format: Option<ColorFormat>,
     orientation: Option<Orientation>,
+    mirror: Option<Mirror>,
+    power: LCDEnableOption<PWR>,
+    power_on_delay_ms: u8,
+    backlight: BL,
+    clear_color: Option<SolidColor>,
+    clear_fill: C,
+    defer_display_on: bool,
 }
 
 impl<B: DisplayBus, P: Panel<B>> DisplayDriverBuilder<B, P> {
@@ -65,9 +104,25 @@ impl<B: DisplayBus, P: Panel<B>> DisplayDriverBuilder<B, P> {
             panel,
             color_format: None,
             orientation: None,
+            mirror: None,
+            power: LCDEnableOption::None,
+            power_on_delay_ms: DEFAULT_POWER_ON_DELAY_MS,
+            backlight: LCDEnableOption::None,
+            clear_color: None,
+            clear_fill: ChunkedClear,
+            defer_display_on: false,
         }
     }
+}
 
+impl<B, P, PWR, BL, C> DisplayDriverBuilder<B, P, PWR, BL, C>
+where
+    B: DisplayBus,
+    P: Panel<B>,
+    PWR: OutputPin,
+    BL: Backlight,
+    C: ClearFill<B>,
+{
     /// Sets the color format to be applied during initialization.
     pub fn with_color_format(mut self, color_format: ColorFormat) -> Self {
         self.color_format = Some(color_format);
@@ -80,37 +135,251 @@ impl<B: DisplayBus, P: Panel<B>> DisplayDriverBuilder<B, P> {
         self
     }
 
+    /// Sets the mirroring to be applied during initialization, after the orientation.
+    pub fn with_mirror(mut self, mirror: Mirror) -> Self {
+        self.mirror = Some(mirror);
+        self
+    }
+
+    /// Sets the LCD power-supply enable pin.
+    ///
+    /// The supply is switched on before the panel is reset, and switched off last on
+    /// [`shutdown`](DisplayDriver::shutdown).
+    pub fn with_power_pin<PWR2: OutputPin>(
+        self,
+        power: LCDEnableOption<PWR2>,
+    ) -> DisplayDriverBuilder<B, P, PWR2, BL, C> {
+        DisplayDriverBuilder {
+            bus: self.bus,
+            panel: self.panel,
+            color_format: self.color_format,
+            orientation: self.orientation,
+            mirror: self.mirror,
+            power,
+            power_on_delay_ms: self.power_on_delay_ms,
+            backlight: self.backlight,
+            clear_color: self.clear_color,
+            clear_fill: self.clear_fill,
+            defer_display_on: self.defer_display_on,
+        }
+    }
+
+    /// Sets the time to wait for the supply to settle after power on (default 20ms).
+    pub fn with_power_on_delay_ms(mut self, delay_ms: u8) -> Self {
+        self.power_on_delay_ms = delay_ms;
+        self
+    }
+
+    /// Sets the backlight enable pin.
+    ///
+    /// The backlight is switched on only after the display has been turned on, and switched off
+    /// first on [`shutdown`](DisplayDriver::shutdown). Brightness is still controlled by the
+    /// panel, see [`DisplayDriver::set_brightness`].
+    pub fn with_backlight_pin<BL2: OutputPin>(
+        self,
+        backlight: LCDEnableOption<BL2>,
+    ) -> DisplayDriverBuilder<B, P, PWR, LCDEnableOption<BL2>, C> {
+        self.with_backlight(backlight)
+    }
+
+    /// Sets the backlight, e.g. a [`PwmBacklight`](panel::backlight::PwmBacklight).
+    ///
+    /// It is sequenced like the backlight enable pin, see
+    /// [`with_backlight_pin`](Self::with_backlight_pin).
+    pub fn with_backlight<BL2: Backlight>(
+        self,
+        backlight: BL2,
+    ) -> DisplayDriverBuilder<B, P, PWR, BL2, C> {
+        DisplayDriverBuilder {
+            bus: self.bus,
+            panel: self.panel,
+            color_format: self.color_format,
+            orientation: self.orientation,
+            mirror: self.mirror,
+            power: self.power,
+            power_on_delay_ms: self.power_on_delay_ms,
+            backlight,
+            clear_color: self.clear_color,
+            clear_fill: self.clear_fill,
+            defer_display_on: self.defer_display_on,
+        }
+    }
+
+    /// Fills the whole screen with `color` while the display is still off.
+    ///
+    /// This hides the random frame memory content shown after power on. The fill is streamed
+    /// through [`DisplayBus::write_pixels`] in small chunks, so it works with every bus, unless
+    /// [`with_clear_color_via_bus`](Self::with_clear_color_via_bus) selected [`BusHardwareFill`].
+    /// The color must match the color format set with
+    /// [`with_color_format`](Self::with_color_format), as seen by the driver (see
+    /// [`DisplayDriver::color_format`]), otherwise [`init`](Self::init) returns
+    /// [`DisplayError::InvalidArgs`].
+    ///
+    /// To write a splash image instead, use
+    /// [`with_deferred_display_on`](Self::with_deferred_display_on).
+    pub fn with_clear_color(mut self, color: SolidColor) -> Self {
+        self.clear_color = Some(color);
+        self
+    }
+
+    /// Leaves the display and backlight off after initialization.
+    ///
+    /// Write the initial content (e.g. with [`DisplayDriver::fill_screen_via_bus`] or
+    /// [`DisplayDriver::write_frame`]), then call [`DisplayDriver::set_display_on`].
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut display = DisplayDriver::builder(bus, panel)
+    ///     .with_color_format(ColorFormat::RGB565)
+    ///     .with_deferred_display_on()
+    ///     .init(&mut delay).await.unwrap();
+    ///
+    /// display.write_frame(SPLASH).await.unwrap();
+    /// display.set_display_on(true).await.unwrap();
+    /// ```
+    pub fn with_deferred_display_on(mut self) -> Self {
+        self.defer_display_on = true;
+        self
+    }
+
     /// Initializes the display and returns the configured [`DisplayDriver`].
     ///
     /// This method:
-    /// 1. Calls the panel's initialization sequence
-    /// 2. Applies the color format if configured
-    /// 3. Applies the orientation if configured
+    /// 1. Switches the power supply on (backlight kept off)
+    /// 2. Calls the panel's initialization sequence (including reset)
+    /// 3. Applies the color format if configured
+    /// 4. Applies the orientation and mirroring if configured
+    /// 5. Fills the screen with the clear color if configured
+    /// 6. Turns the display on, then the backlight (unless deferred)
     pub async fn init<D: DelayNs>(
-        mut self,
+        self,
         delay: &mut D,
-    ) -> Result<DisplayDriver<B, P>, DisplayError<B::Error>> {
-        self.panel
-            .init(&mut self.bus, delay)
-            .await
-            .map_err(DisplayError::BusError)?;
+    ) -> Result<DisplayDriver<B, P, PWR, BL>, DisplayError<B::Error>> {
+        // Check the clear color before touching the hardware
+        let data_format = self
+            .color_format
+            .map(|format| self.bus.pixel_data_format(format));
+        if let (Some(format), Some(color)) = (data_format, &self.clear_color) {
+            if color.format != format {
+                return Err(DisplayError::InvalidArgs);
+            }
+        }
+
+        let mut driver = DisplayDriver {
+            bus: self.bus,
+            panel: self.panel,
+            power: self.power,
+            power_on_delay_ms: self.power_on_delay_ms,
+            brightness: self.backlight.brightness(),
+            backlight: self.backlight,
+            byte_order: PixelByteOrder::BigEndian,
+            color_format: None,
+        };
+
+        driver.power_on_and_init(delay).await?;
 
         if let Some(color_format) = self.color_format {
-            self.panel
-                .set_color_format(&mut self.bus, color_format)
-                .await?;
+            driver.set_color_format(color_format).await?;
         }
 
         if let Some(orientation) = self.orientation {
-            self.panel
-                .set_orientation(&mut self.bus, orientation)
+            driver
+                .panel
+                .set_orientation(&mut driver.bus, orientation)
                 .await?;
         }
 
-        Ok(DisplayDriver {
+        if let Some(mirror) = self.mirror {
+            driver.panel.set_mirror(&mut driver.bus, mirror).await?;
+        }
+
+        if let Some(color) = self.clear_color {
+            C::clear(&mut driver, color).await?;
+        }
+
+        if !self.defer_display_on {
+            driver.display_on_after_init(delay).await?;
+        }
+
+        Ok(driver)
+    }
+}
+
+impl<B, P, PWR, BL, C> DisplayDriverBuilder<B, P, PWR, BL, C>
+where
+    B: DisplayBus + BusHardwareFill,
+    P: Panel<B>,
+    PWR: OutputPin,
+    BL: Backlight,
+{
+    /// Fills the whole screen with `color` using [`BusHardwareFill`] while the display is still
+    /// off, see [`with_clear_color`](Self::with_clear_color).
+    pub fn with_clear_color_via_bus(
+        self,
+        color: SolidColor,
+    ) -> DisplayDriverBuilder<B, P, PWR, BL, BusClear> {
+        DisplayDriverBuilder {
             bus: self.bus,
             panel: self.panel,
-        })
+            color_format: self.color_format,
+            orientation: self.orientation,
+            mirror: self.mirror,
+            power: self.power,
+            power_on_delay_ms: self.power_on_delay_ms,
+            backlight: self.backlight,
+            clear_color: Some(color),
+            clear_fill: BusClear,
+            defer_display_on: self.defer_display_on,
+        }
+    }
+}
+
+/// How [`DisplayDriverBuilder`] fills the screen with the clear color.
+#[allow(async_fn_in_trait)]
+pub trait ClearFill<B: DisplayBus> {
+    /// Fills the whole screen with `color`.
+    async fn clear<P: Panel<B>, PWR: OutputPin, BL: Backlight>(
+        driver: &mut DisplayDriver<B, P, PWR, BL>,
+        color: SolidColor,
+    ) -> Result<(), DisplayError<B::Error>>;
+}
+
+/// Clears the screen through [`DisplayBus::write_pixels`] in small chunks (the default).
+pub struct ChunkedClear;
+
+impl<B: DisplayBus> ClearFill<B> for ChunkedClear {
+    async fn clear<P: Panel<B>, PWR: OutputPin, BL: Backlight>(
+        driver: &mut DisplayDriver<B, P, PWR, BL>,
+        color: SolidColor,
+    ) -> Result<(), DisplayError<B::Error>> {
+        driver.fill_screen_chunked::<CLEAR_CHUNK_SIZE>(color).await
+    }
+}
+
+/// Clears the screen with [`BusHardwareFill`], see
+/// [`DisplayDriverBuilder::with_clear_color_via_bus`].
+pub struct BusClear;
+
+impl<B: DisplayBus + BusHardwareFill> ClearFill<B> for BusClear {
+    async fn clear<P: Panel<B>, PWR: OutputPin, BL: Backlight>(
+        driver: &mut DisplayDriver<B, P, PWR, BL>,
+        color: SolidColor,
+    ) -> Result<(), DisplayError<B::Error>> {
+        driver.fill_screen_via_bus(color).await
+    }
+}
+
+/// Default time to wait after switching the power supply on.
+const DEFAULT_POWER_ON_DELAY_MS: u8 = 20;
+
+/// Chunk buffer size (in bytes) used by [`DisplayDriverBuilder::with_clear_color`].
+const CLEAR_CHUNK_SIZE: usize = 512;
+
+/// Treats an unsupported optional operation as a no-op.
+fn ignore_unsupported<E>(result: Result<(), DisplayError<E>>) -> Result<(), DisplayError<E>> {
+    match result {
+        Err(DisplayError::Unsupported) => Ok(()),
+        result => result,
     }
 }
 
@@ -119,11 +388,28 @@ impl<B: DisplayBus, P: Panel<B>> DisplayDriverBuilder<B, P> {
 /// This struct acts as the "glue" between the logical [`Panel`] implementation (which knows the command set)
 /// and the [`DisplayBus`] (which handles the physical transport). It exposes user-friendly methods
 /// for drawing pixels, filling rectangles, and managing the display state.
-pub struct DisplayDriver<B: DisplayBus, P: Panel<B>> {
+///
+/// Optionally, it also owns the LCD power-supply enable pin (`PWR`) and the backlight (`BL`), see
+/// [`DisplayDriverBuilder::with_power_pin`] and [`DisplayDriverBuilder::with_backlight`].
+pub struct DisplayDriver<B, P, PWR = NoPin, BL = LCDEnableOption<NoPin>>
+where
+    B: DisplayBus,
+    P: Panel<B>,
+    PWR: OutputPin,
+    BL: Backlight,
+{
     /// The underlying bus interface used for communication.
     pub bus: B,
     /// The panel.
     pub panel: P,
+    /// The LCD power-supply enable pin.
+    pub power: LCDEnableOption<PWR>,
+    power_on_delay_ms: u8,
+    /// The backlight.
+    pub backlight: BL,
+    brightness: Option<u8>,
+    byte_order: PixelByteOrder,
+    color_format: Option<ColorFormat>,
 }
 
 impl<B: DisplayBus, P: Panel<B>> DisplayDriver<B, P> {
@@ -144,15 +430,103 @@ impl<B: DisplayBus, P: Panel<B>> DisplayDriver<B, P> {
     ///
     /// Use [`builder`](Self::builder) for a fluent initialization API.
     pub fn new(bus: B, panel: P) -> Self {
-        Self { bus, panel }
+        Self {
+            bus,
+            panel,
+            power: LCDEnableOption::None,
+            power_on_delay_ms: DEFAULT_POWER_ON_DELAY_MS,
+            backlight: LCDEnableOption::None,
+            brightness: None,
+            byte_order: PixelByteOrder::BigEndian,
+            color_format: None,
+        }
     }
+}
 
+impl<B, P, PWR, BL> DisplayDriver<B, P, PWR, BL>
+where
+    B: DisplayBus,
+    P: Panel<B>,
+    PWR: OutputPin,
+    BL: Backlight,
+{
     /// Initializes the display.
+    ///
+    /// Runs the same power-up sequence as [`DisplayDriverBuilder::init`]: power on, reset and
+    /// panel initialization, display on, backlight on. The builder configuration (color format,
+    /// orientation, clear color) is not re-applied.
     pub async fn init(&mut self, delay: &mut impl DelayNs) -> Result<(), DisplayError<B::Error>> {
-        self.panel
-            .init(&mut self.bus, delay)
-            .await
-            .map_err(DisplayError::BusError)
+        self.power_on_and_init(delay).await?;
+        self.display_on_after_init(delay).await
+    }
+
+    /// Switches the power supply on (with the backlight off) and initializes the panel.
+    async fn power_on_and_init(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DisplayError<B::Error>> {
+        self.backlight.set_enabled(false)?;
+
+        if !self.power.is_none() {
+            self.power.set_enabled(true)?;
+            delay.delay_ms(self.power_on_delay_ms as u32).await;
+        }
+
+        // The panel is back in its default format
+        self.color_format = None;
+        self.panel.init(&mut self.bus, &mut *delay).await
+    }
+
+    /// Turns the display on after initialization, waiting [`Panel::DISPLAY_ON_DELAY_MS`] before
+    /// the backlight is enabled.
+    async fn display_on_after_init(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DisplayError<B::Error>> {
+        ignore_unsupported(self.panel.set_display_on(&mut self.bus, true).await)?;
+        delay.delay_ms(P::DISPLAY_ON_DELAY_MS as u32).await;
+        self.backlight.set_enabled(true)
+    }
+
+    /// Shuts the display down in the reverse order of initialization.
+    ///
+    /// Backlight off, display off, sleep in, then power off. Call [`init`](Self::init) to bring
+    /// the display back up.
+    pub async fn shutdown(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DisplayError<B::Error>> {
+        self.set_display_on(false).await?;
+        ignore_unsupported(self.panel.set_sleep(&mut self.bus, true, &mut *delay).await)?;
+
+        if !self.power.is_none() {
+            // Give the controller time to discharge before cutting the supply.
+            delay.delay_ms(P::POWER_OFF_DELAY_MS as u32).await;
+            self.power.set_enabled(false)?;
+        }
+        Ok(())
+    }
+
+    /// Turns the display on or off, sequencing the backlight accordingly.
+    ///
+    /// When turning on, the panel output is enabled before the backlight; when turning off, the
+    /// backlight is disabled first. This avoids showing undefined content.
+    ///
+    /// Unlike [`init`](Self::init), this does not wait [`Panel::DISPLAY_ON_DELAY_MS`] before
+    /// enabling the backlight.
+    pub async fn set_display_on(&mut self, on: bool) -> Result<(), DisplayError<B::Error>> {
+        if on {
+            ignore_unsupported(self.panel.set_display_on(&mut self.bus, true).await)?;
+            self.backlight.set_enabled(true)
+        } else {
+            self.backlight.set_enabled(false)?;
+            ignore_unsupported(self.panel.set_display_on(&mut self.bus, false).await)
+        }
+    }
+
+    /// Switches the backlight (if configured).
+    pub fn set_backlight(&mut self, on: bool) -> Result<(), DisplayError<B::Error>> {
+        self.backlight.set_enabled(on)
     }
 
     /// Sets the window.
@@ -160,14 +534,13 @@ impl<B: DisplayBus, P: Panel<B>> DisplayDriver<B, P> {
     /// Use `write_pixels` or `write_frame` if you just want to draw a buffer.
     /// Use `fill_solid_xxx` if you just want to fill an Area.
     pub async fn set_window(&mut self, area: Area) -> Result<(), DisplayError<B::Error>> {
-        if self.panel.x_alignment() > 1 || self.panel.y_alignment() > 1 {
-            if area.x % self.panel.x_alignment() != 0
-                || area.y % self.panel.y_alignment() != 0
-                || area.w % self.panel.x_alignment() != 0
-                || area.h % self.panel.y_alignment() != 0
-            {
-                return Err(DisplayError::UnalignedArea);
-            }
+        let (x_align, y_align) = (self.panel.x_alignment(), self.panel.y_alignment());
+        if !area.x.is_multiple_of(x_align)
+            || !area.y.is_multiple_of(y_align)
+            || !area.w.is_multiple_of(x_align)
+            || !area.h.is_multiple_of(y_align)
+        {
+            return Err(DisplayError::UnalignedArea);
         }
 
         if area.w == 0 || area.h == 0 {
@@ -187,7 +560,19 @@ impl<B: DisplayBus, P: Panel<B>> DisplayDriver<B, P> {
     ) -> Result<(), DisplayError<B::Error>> {
         self.panel
             .set_color_format(&mut self.bus, color_format)
-            .await
+            .await?;
+        self.color_format = Some(self.bus.pixel_data_format(color_format));
+        Ok(())
+    }
+
+    /// Returns the color format of the pixel data, or `None` if the panel is in its default
+    /// format.
+    ///
+    /// This is the format set with [`set_color_format`](Self::set_color_format) or
+    /// [`DisplayDriverBuilder::with_color_format`], unless the bus converts the pixel data, see
+    /// [`DisplayBus::pixel_data_format`].
+    pub fn color_format(&self) -> Option<ColorFormat> {
+        self.color_format
     }
 
     /// Sets the display orientation.
@@ -198,6 +583,73 @@ impl<B: DisplayBus, P: Panel<B>> DisplayDriver<B, P> {
         self.panel.set_orientation(&mut self.bus, orientation).await
     }
 
+    /// Sets the display mirroring, on top of the orientation.
+    pub async fn set_mirror(&mut self, mirror: Mirror) -> Result<(), DisplayError<B::Error>> {
+        self.panel.set_mirror(&mut self.bus, mirror).await
+    }
+
+    /// Sets the byte order of the pixel buffers passed to [`write_pixels`](Self::write_pixels),
+    /// [`write_pixel_rows`](Self::write_pixel_rows) and the methods based on them.
+    ///
+    /// With [`PixelByteOrder::LittleEndian`], the two bytes of each 16-bit pixel are swapped: by
+    /// the bus if it supports [`HARDWARE_BYTE_SWAP`](DisplayBus::HARDWARE_BYTE_SWAP), otherwise in
+    /// software through a small chunk buffer. This allows sending native little-endian RGB565
+    /// buffers. [`SolidColor`] and the fill methods are not affected.
+    ///
+    /// Little-endian data only exists for 16-bit colors: while it is set, pixel writes fail with
+    /// [`DisplayError::Unsupported`] if the [`color_format`](Self::color_format) is another one.
+    pub fn set_pixel_byte_order(&mut self, byte_order: PixelByteOrder) {
+        self.byte_order = byte_order;
+    }
+
+    /// Returns the byte order of pixel buffers.
+    pub fn pixel_byte_order(&self) -> PixelByteOrder {
+        self.byte_order
+    }
+
+    /// Sends pixel data with the pixel write command, swapping bytes as configured.
+    pub(crate) async fn send_pixels(
+        &mut self,
+        data: &[u8],
+        mut metadata: Metadata,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let cmd = &P::PIXEL_WRITE_CMD[0..P::CMD_LEN];
+        if self.byte_order == PixelByteOrder::LittleEndian
+            && self
+                .color_format
+                .is_some_and(|format| format.size_bits() != 16)
+        {
+            return Err(DisplayError::Unsupported);
+        }
+        if self.byte_order == PixelByteOrder::BigEndian || B::HARDWARE_BYTE_SWAP {
+            metadata.byte_order = self.byte_order;
+            return self.bus.write_pixels(cmd, data, metadata).await;
+        }
+        if !data.len().is_multiple_of(2) {
+            return Err(DisplayError::InvalidArgs);
+        }
+        if data.is_empty() {
+            // Nothing to swap, but the transfer may still end the frame.
+            return self.bus.write_pixels(cmd, data, metadata).await;
+        }
+
+        let mut buffer = [0u8; SWAP_CHUNK_BYTES];
+        let mut chunks = data.chunks(SWAP_CHUNK_BYTES).peekable();
+        let frame_control = metadata.frame_control;
+        metadata.frame_control.last = false;
+        while let Some(chunk) = chunks.next() {
+            let buffer = &mut buffer[..chunk.len()];
+            for (dst, src) in buffer.chunks_exact_mut(2).zip(chunk.chunks_exact(2)) {
+                dst.copy_from_slice(&[src[1], src[0]]);
+            }
+            metadata.frame_control.last = frame_control.last && chunks.peek().is_none();
+
+            self.bus.write_pixels(cmd, buffer, metadata).await?;
+            metadata = Metadata::new_continue_stream();
+        }
+        Ok(())
+    }
+
     /// Writes pixels to the specified area.
     pub async fn write_pixels(
         &mut self,
@@ -206,12 +658,161 @@ impl<B: DisplayBus, P: Panel<B>> DisplayDriver<B, P> {
         buffer: &[u8],
     ) -> Result<(), DisplayError<B::Error>> {
         self.set_window(area).await?;
-        let cmd = &P::PIXEL_WRITE_CMD[0..P::CMD_LEN];
-        let metadata = Metadata {
-            area: Some(area),
-            frame_control,
+        let metadata = Metadata::new_from_parts(Some(area), frame_control);
+        self.send_pixels(buffer, metadata).await
+    }
+
+    /// Writes pixels to the specified area, given as one byte slice per row.
+    ///
+    /// This allows writing a sub-area of a larger framebuffer without copying it. The rows are
+    /// streamed as a single transfer.
+    pub async fn write_pixel_rows<'r>(
+        &mut self,
+        area: Area,
+        frame_control: FrameControl,
+        rows: impl IntoIterator<Item = &'r [u8]>,
+    ) -> Result<(), DisplayError<B::Error>> {
+        self.set_window(area).await?;
+
+        let mut rows = rows.into_iter().peekable();
+        let mut metadata = Metadata::new_from_parts(
+            Some(area),
+            FrameControl {
+                first: frame_control.first,
+                last: false,
+            },
+        );
+        while let Some(row) = rows.next() {
+            metadata.frame_control.last = frame_control.last && rows.peek().is_none();
+            self.send_pixels(row, metadata).await?;
+            metadata = Metadata::new_continue_stream();
+        }
+        Ok(())
+    }
+
+    /// Writes pixels to the specified area from an iterator of colors, e.g. embedded-graphics
+    /// colors or procedurally generated content.
+    ///
+    /// The colors are serialized into an `N` byte buffer in the active
+    /// [`color_format`](Self::color_format) and streamed chunk by chunk, so no framebuffer is
+    /// needed. Exactly `area.total_pixels()` colors are taken. Colors of another RGB format are
+    /// converted; if no format has been set, the format of the first color is used.
+    ///
+    /// Packed formats (e.g. RGB444, Gray4) are packed MSB first; `N` must then hold at least one
+    /// [`SolidColor::pattern`].
+    ///
+    /// Returns [`DisplayError::InvalidArgs`] if `N` is smaller than one pixel, if a color can't be
+    /// converted, or if `colors` ends early. In the latter cases, the pixels before are written
+    /// without marking the end of the frame.
+    pub async fn write_pixels_iter<const N: usize, C: Into<SolidColor>>(
+        &mut self,
+        area: Area,
+        colors: impl IntoIterator<Item = C>,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let total_pixels = area.total_pixels();
+        let mut colors = colors
+            .into_iter()
+            .take(total_pixels)
+            .map(Into::<SolidColor>::into)
+            .peekable();
+        let format = match (self.color_format, colors.peek()) {
+            (Some(format), _) => format,
+            (None, Some(color)) => color.format,
+            (None, None) => return Err(DisplayError::InvalidArgs),
         };
-        self.bus.write_pixels(cmd, buffer, metadata).await
+        let (pattern_bytes, pattern_pixels) = format.pattern_size();
+        let pixels_per_chunk = N / pattern_bytes * pattern_pixels;
+        if total_pixels == 0 || pixels_per_chunk == 0 {
+            return Err(DisplayError::InvalidArgs);
+        }
+        let bits = format.wire_bits() as usize;
+
+        self.set_window(area).await?;
+        let cmd = &P::PIXEL_WRITE_CMD[0..P::CMD_LEN];
+
+        let mut buffer = [0u8; N];
+        let mut written_pixels = 0;
+        let mut metadata = Metadata::new_from_parts(Some(area), FrameControl::new_first());
+        loop {
+            let mut pixels = 0;
+            while pixels < pixels_per_chunk {
+                let Some(color) = colors.next() else {
+                    break;
+                };
+                let color = color.to_format(format).ok_or(DisplayError::InvalidArgs)?;
+                color::pack_pixel(&mut buffer, pixels * bits, &color.raw, bits);
+                pixels += 1;
+            }
+            if pixels == 0 {
+                break;
+            }
+            written_pixels += pixels;
+            // Only mark the end of the frame once all pixels are there
+            metadata.frame_control.last = written_pixels == total_pixels;
+
+            let len = format.bytes_for(pixels);
+            self.bus.write_pixels(cmd, &buffer[..len], metadata).await?;
+            if metadata.frame_control.last {
+                return Ok(());
+            }
+            metadata = Metadata::new_continue_stream();
+        }
+        Err(DisplayError::InvalidArgs)
+    }
+
+    /// Returns `area` expanded to the panel's alignment, see [`Area::expand_to_alignment`].
+    pub fn align_area(&self, area: Area) -> Area {
+        area.expand_to_alignment(self.panel.x_alignment(), self.panel.y_alignment())
+    }
+
+    /// Writes `area` from a larger buffer, expanding it to the panel's alignment first.
+    ///
+    /// `buffer` holds the pixels of `buffer_area` (row by row, without padding), e.g. a
+    /// framebuffer. The pixels added by the expansion are taken from it, so they are rewritten
+    /// with their current content. This makes arbitrary partial updates work on panels that
+    /// require an aligned window, such as the CO5300.
+    ///
+    /// # Errors
+    /// * [`DisplayError::InvalidArgs`] if `area` is empty or not within `buffer_area`, or the
+    ///   buffer size doesn't match `buffer_area`.
+    /// * [`DisplayError::UnalignedArea`] if the expanded area doesn't fit in `buffer_area`.
+    /// * [`DisplayError::Unsupported`] for color formats with less than one byte per pixel, i.e.
+    ///   if `buffer` is smaller than the number of pixels in `buffer_area`.
+    pub async fn write_pixels_from(
+        &mut self,
+        area: Area,
+        frame_control: FrameControl,
+        buffer: &[u8],
+        buffer_area: Area,
+    ) -> Result<(), DisplayError<B::Error>> {
+        if area.w == 0 || area.h == 0 || !area.is_within(&buffer_area) {
+            return Err(DisplayError::InvalidArgs);
+        }
+        let pixels = buffer_area.total_pixels();
+        if pixels == 0 {
+            return Err(DisplayError::InvalidArgs);
+        }
+        // Sub-byte formats hold less than one byte per pixel
+        if buffer.len() < pixels {
+            return Err(DisplayError::Unsupported);
+        }
+        if !buffer.len().is_multiple_of(pixels) {
+            return Err(DisplayError::InvalidArgs);
+        }
+        let pixel_size = buffer.len() / pixels;
+
+        let area = self.align_area(area);
+        if !area.is_within(&buffer_area) {
+            return Err(DisplayError::UnalignedArea);
+        }
+
+        let stride = buffer_area.w as usize * pixel_size;
+        let (x, y) = (area.x - buffer_area.x, area.y - buffer_area.y);
+        let rows = (y..y + area.h).map(|row| {
+            let start = row as usize * stride + x as usize * pixel_size;
+            &buffer[start..start + area.w as usize * pixel_size]
+        });
+        self.write_pixel_rows(area, frame_control, rows).await
     }
 
     /// Writes the entire buffer to the display.
@@ -223,16 +824,114 @@ impl<B: DisplayBus, P: Panel<B>> DisplayDriver<B, P> {
         )
         .await
     }
+
+    /// Fills the area with a solid color, streamed through [`DisplayBus::write_pixels`] in chunks
+    /// of at most `N` bytes.
+    ///
+    /// Unlike [`fill_solid_batch`](Self::fill_solid_batch) and
+    /// [`fill_solid_via_bus`](Self::fill_solid_via_bus), this works with every bus.
+    pub async fn fill_solid_chunked<const N: usize>(
+        &mut self,
+        color: SolidColor,
+        area: Area,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let (pattern, pattern_bytes, pattern_pixels) = color.pattern();
+        let pixels_per_chunk = N / pattern_bytes * pattern_pixels;
+        if pixels_per_chunk == 0 {
+            return Err(DisplayError::InvalidArgs);
+        }
+
+        self.set_window(area).await?;
+        let cmd = &P::PIXEL_WRITE_CMD[0..P::CMD_LEN];
+
+        // Pre-fill the buffer with the color pattern
+        let mut buffer = [0u8; N];
+        for chunk in buffer.chunks_exact_mut(pattern_bytes) {
+            chunk.copy_from_slice(&pattern[..pattern_bytes]);
+        }
+
+        let mut remaining_pixels = area.total_pixels();
+        let mut metadata = Metadata::new_from_parts(Some(area), FrameControl::new_first());
+        while remaining_pixels > 0 {
+            let current_pixels = remaining_pixels.min(pixels_per_chunk);
+            remaining_pixels -= current_pixels;
+            metadata.frame_control.last = remaining_pixels == 0;
+
+            let len = color.format.bytes_for(current_pixels);
+            self.bus.write_pixels(cmd, &buffer[..len], metadata).await?;
+            metadata = Metadata::new_continue_stream();
+        }
+
+        Ok(())
+    }
+
+    /// Fills the entire screen with a solid color, see [`fill_solid_chunked`](Self::fill_solid_chunked).
+    pub async fn fill_screen_chunked<const N: usize>(
+        &mut self,
+        color: SolidColor,
+    ) -> Result<(), DisplayError<B::Error>> {
+        self.fill_solid_chunked::<N>(color, Area::from_origin_size(self.panel.size()))
+            .await
+    }
 }
 
-impl<B: DisplayBus, P: Panel<B> + PanelSetBrightness<B>> DisplayDriver<B, P> {
-    /// Sets the display brightness (if supported by the panel).
+impl<B, P, PWR, BL> DisplayDriver<B, P, PWR, BL>
+where
+    B: DisplayBus,
+    P: Panel<B>,
+    PWR: OutputPin,
+    BL: BrightnessControl<B, P>,
+{
+    /// Sets the display brightness (0-255).
+    ///
+    /// Depending on the backlight, this uses the panel's own brightness command (see
+    /// [`PanelSetBrightness`]) or an external PWM (see
+    /// [`PwmBacklight`](panel::backlight::PwmBacklight)).
     pub async fn set_brightness(&mut self, brightness: u8) -> Result<(), DisplayError<B::Error>> {
-        self.panel.set_brightness(&mut self.bus, brightness).await
+        self.backlight
+            .set_brightness(&mut self.bus, &mut self.panel, brightness)
+            .await?;
+        self.brightness = Some(brightness);
+        Ok(())
+    }
+
+    /// Returns the last brightness set with [`set_brightness`](Self::set_brightness).
+    ///
+    /// Before that, this is the backlight's own brightness (see [`Backlight::brightness`]), or
+    /// `None` if it is controlled by the panel and unknown.
+    pub fn brightness(&self) -> Option<u8> {
+        self.brightness
+    }
+
+    /// Fades from the current brightness to `target` over `duration_ms`.
+    ///
+    /// The brightness is updated every [`FADE_STEP_MS`](panel::backlight::FADE_STEP_MS). If the
+    /// current brightness is unknown, see [`brightness`](Self::brightness), it is set to `target`
+    /// right away.
+    pub async fn fade_brightness(
+        &mut self,
+        target: u8,
+        duration_ms: u32,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let Some(from) = self.brightness else {
+            return self.set_brightness(target).await;
+        };
+        for brightness in FadeSteps::new(from, target, duration_ms) {
+            delay.delay_ms(FADE_STEP_MS).await;
+            self.set_brightness(brightness).await?;
+        }
+        Ok(())
     }
 }
 
-impl<B: DisplayBus + BusHardwareFill, P: Panel<B>> DisplayDriver<B, P> {
+impl<B, P, PWR, BL> DisplayDriver<B, P, PWR, BL>
+where
+    B: DisplayBus + BusHardwareFill,
+    P: Panel<B>,
+    PWR: OutputPin,
+    BL: Backlight,
+{
     /// Fills the area with a solid color using bus auto-fill.
     pub async fn fill_solid_via_bus(
         &mut self,
@@ -254,10 +953,12 @@ impl<B: DisplayBus + BusHardwareFill, P: Panel<B>> DisplayDriver<B, P> {
     }
 }
 
-impl<B, P> DisplayDriver<B, P>
+impl<B, P, PWR, BL> DisplayDriver<B, P, PWR, BL>
 where
     B: DisplayBus + BusBytesIo,
     P: Panel<B>,
+    PWR: OutputPin,
+    BL: Backlight,
 {
     /// Fills the area with a solid color.
     pub async fn fill_solid_batch<const N: usize>(
@@ -265,6 +966,15 @@ where
         color: SolidColor,
         area: Area,
     ) -> Result<(), DisplayError<B::Error>> {
+        // The color as the shortest byte pattern, which packs several pixels for packed formats
+        let (pattern, pattern_bytes, pattern_pixels) = color.pattern();
+
+        // Calculate how many full patterns fit in the buffer
+        let pixels_per_chunk = N / pattern_bytes * pattern_pixels;
+        if pixels_per_chunk == 0 {
+            return Err(DisplayError::InvalidArgs);
+        }
+
         self.set_window(area).await?;
         let cmd = &P::PIXEL_WRITE_CMD[0..P::CMD_LEN];
 
@@ -273,26 +983,19 @@ where
             .await
             .map_err(DisplayError::BusError)?;
 
-        let pixel_size = color.format.size_bytes() as usize;
         let total_pixels = area.total_pixels();
         let mut remaining_pixels = total_pixels;
 
         let mut buffer = [0u8; N];
 
-        // Calculate how many full pixels fit in the buffer
-        let pixels_per_chunk = buffer.len() / pixel_size;
-
-        // Extract the raw bytes for the color based on its size
-        let color_bytes = &color.raw[..pixel_size];
-
         // Pre-fill the buffer with the color pattern
-        for i in 0..pixels_per_chunk {
-            buffer[i * pixel_size..(i + 1) * pixel_size].copy_from_slice(color_bytes);
+        for chunk in buffer.chunks_exact_mut(pattern_bytes) {
+            chunk.copy_from_slice(&pattern[..pattern_bytes]);
         }
 
         while remaining_pixels > 0 {
             let current_pixels = remaining_pixels.min(pixels_per_chunk);
-            let byte_count = current_pixels * pixel_size;
+            let byte_count = color.format.bytes_for(current_pixels);
             self.bus
                 .write_data_bytes(&buffer[0..byte_count])
                 .await
@@ -312,3 +1015,125 @@ where
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::pwm::{ErrorType as PwmErrorType, SetDutyCycle};
+
+    extern crate std;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::{
+        panel::backlight::{BrightnessCurve, PwmBacklight},
+        test_util::{block_on, MockBus, MockPanel, NoDelay},
+    };
+
+    /// A PWM channel remembering its duty cycle.
+    struct MockPwm(u16);
+
+    impl PwmErrorType for MockPwm {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SetDutyCycle for MockPwm {
+        fn max_duty_cycle(&self) -> u16 {
+            255
+        }
+
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+            self.0 = duty;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn clear_color_must_match_color_format() {
+        let builder = |color| {
+            DisplayDriver::builder(MockBus::<false>::default(), MockPanel::new(2, 1))
+                .with_color_format(ColorFormat::RGB666)
+                .with_clear_color(SolidColor::from_rgb888(color, [0xFF; 3]).unwrap())
+        };
+        assert!(matches!(
+            block_on(builder(ColorFormat::RGB565).init(&mut NoDelay)),
+            Err(DisplayError::InvalidArgs)
+        ));
+
+        let driver = block_on(builder(ColorFormat::RGB666).init(&mut NoDelay)).unwrap();
+        assert_eq!(driver.bus.pixels(), [0xFC; 6]);
+    }
+
+    #[test]
+    fn brightness_starts_at_the_backlight_brightness() {
+        let backlight = PwmBacklight::new(MockPwm(0)).with_curve(BrightnessCurve::Linear);
+        let mut driver = block_on(
+            DisplayDriver::builder(MockBus::<false>::default(), MockPanel::new(2, 1))
+                .with_backlight(backlight)
+                .init(&mut NoDelay),
+        )
+        .unwrap();
+        assert_eq!(driver.brightness(), Some(255));
+
+        block_on(driver.fade_brightness(51, 50, &mut NoDelay)).unwrap();
+        assert_eq!(driver.brightness(), Some(51));
+        assert_eq!(driver.backlight.release().0, 51);
+    }
+
+    #[test]
+    fn little_endian_requires_16_bit_colors() {
+        let mut driver = DisplayDriver::new(MockBus::<false>::default(), MockPanel::new(2, 1));
+        driver.set_pixel_byte_order(PixelByteOrder::LittleEndian);
+        let area = Area::from_origin(2, 1);
+        block_on(driver.set_color_format(ColorFormat::RGB666)).unwrap();
+        assert!(matches!(
+            block_on(driver.write_pixels(area, FrameControl::new_standalone(), &[0; 6])),
+            Err(DisplayError::Unsupported)
+        ));
+
+        block_on(driver.set_color_format(ColorFormat::RGB565)).unwrap();
+        block_on(driver.write_pixels(area, FrameControl::new_standalone(), &[1, 2, 3, 4])).unwrap();
+        assert_eq!(driver.bus.pixels(), [2, 1, 4, 3]);
+    }
+
+    #[test]
+    fn write_pixels_iter_streams_chunks() {
+        let mut driver = DisplayDriver::new(MockBus::<false>::default(), MockPanel::new(3, 1));
+        let area = Area::from_origin(3, 1);
+        let colors = [0x12, 0x34, 0x56]
+            .map(|v| SolidColor::from_rgb888(ColorFormat::RGB888, [v; 3]).unwrap());
+        block_on(driver.write_pixels_iter::<6, _>(area, colors.clone())).unwrap();
+
+        let writes: Vec<_> = driver
+            .bus
+            .pixel_writes
+            .iter()
+            .map(|write| {
+                let frame_control = write.metadata.frame_control;
+                (write.data.len(), frame_control.first, frame_control.last)
+            })
+            .collect();
+        assert_eq!(writes, [(6, true, false), (3, false, true)]);
+        assert_eq!(driver.bus.pixels()[3..6], [0x34; 3]);
+
+        // Missing colors never end the frame
+        driver.bus.pixel_writes.clear();
+        assert!(matches!(
+            block_on(driver.write_pixels_iter::<6, _>(area, colors.into_iter().take(2))),
+            Err(DisplayError::InvalidArgs)
+        ));
+        assert!(!driver.bus.pixel_writes[0].metadata.frame_control.last);
+    }
+
+    #[test]
+    fn empty_last_chunk_is_forwarded() {
+        let mut driver = DisplayDriver::new(MockBus::<false>::default(), MockPanel::new(2, 1));
+        driver.set_pixel_byte_order(PixelByteOrder::LittleEndian);
+        block_on(driver.send_pixels(
+            &[],
+            Metadata::new_from_parts(None, FrameControl::new_last()),
+        ))
+        .unwrap();
+        assert_eq!(driver.bus.pixel_writes.len(), 1);
+        assert!(driver.bus.pixel_writes[0].metadata.frame_control.last);
+    }
+}
@@ -0,0 +1,251 @@
+use embedded_hal::digital::OutputPin;
+use embedded_hal::pwm::{Error, SetDutyCycle};
+
+use crate::{DisplayBus, DisplayError};
+
+use super::{power::LCDEnableOption, Panel, PanelSetBrightness};
+
+/// Interval between two brightness steps of a fade.
+pub const FADE_STEP_MS: u32 = 10;
+
+/// A backlight that can be switched on and off by the driver.
+///
+/// Used by [`DisplayDriver`](crate::DisplayDriver) to sequence the backlight after the display is
+/// turned on, and before it is turned off.
+pub trait Backlight {
+    /// Switches the backlight on or off.
+    ///
+    /// Switching on restores the last brightness, if the backlight supports dimming.
+    fn set_enabled<E>(&mut self, enabled: bool) -> Result<(), DisplayError<E>>;
+
+    /// Returns the current brightness (0-255), or `None` if the backlight doesn't control it.
+    fn brightness(&self) -> Option<u8> {
+        None
+    }
+}
+
+/// Brightness control for a display, either by the backlight itself or through the panel.
+///
+/// This lets [`DisplayDriver::set_brightness`](crate::DisplayDriver::set_brightness) offer one
+/// brightness API regardless of how the brightness is actually controlled:
+/// - [`LCDEnableOption`] (on/off pin or no pin) forwards to the panel's DCS brightness command
+///   via [`PanelSetBrightness`].
+/// - [`PwmBacklight`] drives an external PWM.
+#[allow(async_fn_in_trait)]
+pub trait BrightnessControl<B: DisplayBus, P: Panel<B>>: Backlight {
+    /// Sets the brightness, between 0 (minimum) and 255 (maximum).
+    async fn set_brightness(
+        &mut self,
+        bus: &mut B,
+        panel: &mut P,
+        brightness: u8,
+    ) -> Result<(), DisplayError<B::Error>>;
+}
+
+impl<PIN: OutputPin> Backlight for LCDEnableOption<PIN> {
+    fn set_enabled<E>(&mut self, enabled: bool) -> Result<(), DisplayError<E>> {
+        LCDEnableOption::set_enabled(self, enabled)
+    }
+}
+
+impl<B, P, PIN> BrightnessControl<B, P> for LCDEnableOption<PIN>
+where
+    B: DisplayBus,
+    P: Panel<B> + PanelSetBrightness<B>,
+    PIN: OutputPin,
+{
+    async fn set_brightness(
+        &mut self,
+        bus: &mut B,
+        panel: &mut P,
+        brightness: u8,
+    ) -> Result<(), DisplayError<B::Error>> {
+        panel.set_brightness(bus, brightness).await
+    }
+}
+
+/// Mapping from the requested brightness to the PWM duty cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrightnessCurve {
+    /// The duty cycle is proportional to the requested brightness.
+    Linear,
+    /// The requested brightness is treated as perceived lightness (CIE 1931 L*), so equal steps
+    /// look equally large to the eye. This is the default.
+    Cie1931,
+}
+
+impl BrightnessCurve {
+    /// Maps `brightness` (0-255) to a duty cycle between 0 and `max_duty`.
+    pub fn duty(self, brightness: u8, max_duty: u16) -> u16 {
+        let max_duty = max_duty as u64;
+        let duty = match self {
+            BrightnessCurve::Linear => brightness as u64 * max_duty / 255,
+            BrightnessCurve::Cie1931 => {
+                // L* = brightness * 100 / 255, computed in units of 1/255.
+                let l = brightness as u64 * 100;
+                if l <= 8 * 255 {
+                    // Y = L* / 903.3
+                    l * 10 * max_duty / (255 * 9033)
+                } else {
+                    // Y = ((L* + 16) / 116)^3
+                    let num = l + 16 * 255;
+                    let den = 116 * 255;
+                    num * num * num * max_duty / (den * den * den)
+                }
+            }
+        };
+        duty as u16
+    }
+}
+
+/// A PWM-driven backlight, built on [`SetDutyCycle`].
+///
+/// Brightness values are perceptual by default (see [`BrightnessCurve::Cie1931`]), and can be
+/// faded smoothly with [`DisplayDriver::fade_brightness`](crate::DisplayDriver::fade_brightness).
+///
+/// # Example
+/// ```ignore
+/// let backlight = PwmBacklight::new(pwm_channel);
+/// let mut display = DisplayDriver::builder(bus, panel)
+///     .with_backlight(backlight)
+///     .init(&mut delay).await.unwrap();
+///
+/// display.fade_brightness(64, 500, &mut delay).await.unwrap();
+/// ```
+pub struct PwmBacklight<PWM: SetDutyCycle> {
+    pwm: PWM,
+    curve: BrightnessCurve,
+    active_low: bool,
+    brightness: u8,
+    enabled: bool,
+}
+
+impl<PWM: SetDutyCycle> PwmBacklight<PWM> {
+    /// Creates a new PWM backlight with the perceptual brightness curve.
+    ///
+    /// The backlight starts disabled, with brightness set to the maximum.
+    pub fn new(pwm: PWM) -> Self {
+        Self {
+            pwm,
+            curve: BrightnessCurve::Cie1931,
+            active_low: false,
+            brightness: 255,
+            enabled: false,
+        }
+    }
+
+    /// Sets the brightness curve.
+    pub fn with_curve(mut self, curve: BrightnessCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Inverts the duty cycle, for backlight drivers that are on while the PWM output is low.
+    pub fn with_active_low(mut self) -> Self {
+        self.active_low = true;
+        self
+    }
+
+    /// Releases the PWM channel.
+    pub fn release(self) -> PWM {
+        self.pwm
+    }
+
+    /// Returns the current brightness (0-255), also while disabled.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Returns whether the backlight is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Sets the brightness (0-255).
+    ///
+    /// If the backlight is disabled, the value is only stored and applied when it is enabled.
+    pub fn set_brightness<E>(&mut self, brightness: u8) -> Result<(), DisplayError<E>> {
+        self.brightness = brightness;
+        if self.enabled {
+            self.apply(brightness)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn apply<E>(&mut self, brightness: u8) -> Result<(), DisplayError<E>> {
+        let max_duty = self.pwm.max_duty_cycle();
+        let mut duty = self.curve.duty(brightness, max_duty);
+        if self.active_low {
+            duty = max_duty - duty;
+        }
+        self.pwm
+            .set_duty_cycle(duty)
+            .map_err(|err| DisplayError::PwmError(err.kind()))
+    }
+}
+
+impl<PWM: SetDutyCycle> Backlight for PwmBacklight<PWM> {
+    fn set_enabled<E>(&mut self, enabled: bool) -> Result<(), DisplayError<E>> {
+        self.enabled = enabled;
+        self.apply(if enabled { self.brightness } else { 0 })
+    }
+
+    fn brightness(&self) -> Option<u8> {
+        Some(self.brightness)
+    }
+}
+
+impl<B, P, PWM> BrightnessControl<B, P> for PwmBacklight<PWM>
+where
+    B: DisplayBus,
+    P: Panel<B>,
+    PWM: SetDutyCycle,
+{
+    async fn set_brightness(
+        &mut self,
+        _bus: &mut B,
+        _panel: &mut P,
+        brightness: u8,
+    ) -> Result<(), DisplayError<B::Error>> {
+        PwmBacklight::set_brightness(self, brightness)
+    }
+}
+
+/// Iterator over the intermediate brightness values of a fade, one every [`FADE_STEP_MS`].
+///
+/// The last value is always the target.
+#[derive(Clone, Debug)]
+pub struct FadeSteps {
+    from: u8,
+    to: u8,
+    steps: u32,
+    step: u32,
+}
+
+impl FadeSteps {
+    /// Creates the steps for fading from `from` to `to` over `duration_ms`.
+    pub fn new(from: u8, to: u8, duration_ms: u32) -> Self {
+        Self {
+            from,
+            to,
+            steps: (duration_ms / FADE_STEP_MS).max(1),
+            step: 0,
+        }
+    }
+}
+
+impl Iterator for FadeSteps {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.step >= self.steps {
+            return None;
+        }
+        self.step += 1;
+
+        let from = self.from as i64;
+        let delta = self.to as i64 - from;
+        Some((from + delta * self.step as i64 / self.steps as i64) as u8)
+    }
+}
@@ -3,7 +3,7 @@ use embedded_hal_async::delay::DelayNs;
 use crate::DisplayBus;
 
 /// A step in the initialization sequence.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum InitStep<'a> {
     /// Single byte command.
     SingleCommand(u8),
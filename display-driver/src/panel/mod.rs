@@ -2,8 +2,13 @@ use embedded_hal_async::delay::DelayNs;
 
 use crate::{bus::BusRead, ColorFormat, DisplayBus, DisplayError};
 
+pub mod backlight;
 pub mod initseq;
+pub mod power;
 pub mod reset;
+pub mod shape;
+
+use shape::VisibleShape;
 
 /// Display orientation.
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -26,6 +31,21 @@ impl Orientation {
     }
 }
 
+/// Display mirroring, applied on top of the [`Orientation`].
+///
+/// Together with the four orientations, this covers all eight combinations of rotation and
+/// mirroring, e.g. for displays viewed through a mirror (HUDs) or panels mounted flipped.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mirror {
+    /// No mirroring.
+    #[default]
+    None,
+    /// Mirrored left to right.
+    Horizontal,
+    /// Mirrored top to bottom.
+    Vertical,
+}
+
 /// A trait representing a specific display panel model (e.g., ST7789, ILI9341).
 ///
 /// While [`DisplayBus`] handles *how* data is sent to the screen, this `Panel` trait handles *what*
@@ -38,10 +58,17 @@ pub trait Panel<B: DisplayBus> {
     /// The specific command byte(s) used to initiate a pixel write operation to the display's RAM.
     /// For many MIPI DCS compliant displays, this is `0x2C` (RAMWR).
     ///
-    /// Note: We can't use `[u8; Self::CMD_LEN]` in stable Rust constants yet, so we use a reference 
+    /// Note: We can't use `[u8; Self::CMD_LEN]` in stable Rust constants yet, so we use a reference
     /// slice `&PIXEL_WRITE_CMD[0..P::CMD_LEN]` when using this.
     const PIXEL_WRITE_CMD: [u8; 4];
 
+    /// Time to wait after the display output is first turned on, before the backlight is
+    /// enabled, in milliseconds.
+    const DISPLAY_ON_DELAY_MS: u8 = 0;
+
+    /// Time to wait between entering sleep mode and cutting the power supply in milliseconds.
+    const POWER_OFF_DELAY_MS: u8 = 120;
+
     /// Returns the display width, accounting for orientation.
     fn width(&self) -> u16;
 
@@ -63,15 +90,31 @@ pub trait Panel<B: DisplayBus> {
         1
     }
 
+    /// Returns the visible shape of the display glass (the full rectangle by default).
+    fn visible_shape(&self) -> VisibleShape {
+        VisibleShape::Rectangle
+    }
+
     /// Initializes the panel.
-    async fn init<D: DelayNs>(&mut self, bus: &mut B, delay: D) -> Result<(), B::Error>;
+    ///
+    /// The display output should be left off, so that the frame memory can be cleared or preloaded
+    /// before [`set_display_on`](Self::set_display_on) is called by the driver.
+    ///
+    /// This includes the hardware reset, so errors from the reset pin are reported as
+    /// [`DisplayError::PinError`] and unsupported reset methods as
+    /// [`DisplayError::ResetUnsupported`].
+    async fn init<D: DelayNs>(
+        &mut self,
+        bus: &mut B,
+        delay: D,
+    ) -> Result<(), DisplayError<B::Error>>;
 
     /// Sets the active drawing window on the display.
     ///
     /// This method translates the abstract coordinates (x0, y0, x1, y1) into the specific "Column
     /// Address Set" and "Page Address Set" commands understood by the display controller.
     ///
-    /// Note: For some monochrome displays or AMOLED panels, coordinates must be aligned to 
+    /// Note: For some monochrome displays or AMOLED panels, coordinates must be aligned to
     /// `self.x_alignment()` and `self.y_alignment()`.
     async fn set_window(
         &mut self,
@@ -107,6 +150,41 @@ pub trait Panel<B: DisplayBus> {
         Err(DisplayError::Unsupported)
     }
 
+    /// Sets the display mirroring, on top of the orientation.
+    async fn set_mirror(
+        &mut self,
+        bus: &mut B,
+        mirror: Mirror,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let _ = (bus, mirror);
+        Err(DisplayError::Unsupported)
+    }
+
+    /// Turns the display output on or off (e.g. DCS `SET_DISPLAY_ON` / `SET_DISPLAY_OFF`).
+    ///
+    /// The frame memory content is retained while the display is off.
+    async fn set_display_on(
+        &mut self,
+        bus: &mut B,
+        on: bool,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let _ = (bus, on);
+        Err(DisplayError::Unsupported)
+    }
+
+    /// Enters or exits sleep mode (e.g. DCS `ENTER_SLEEP_MODE` / `EXIT_SLEEP_MODE`).
+    ///
+    /// Implementations should wait for the controller to settle before returning.
+    async fn set_sleep<D: DelayNs>(
+        &mut self,
+        bus: &mut B,
+        sleep: bool,
+        delay: D,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let _ = (bus, sleep, delay);
+        Err(DisplayError::Unsupported)
+    }
+
     /// Configures the pixel color format (e.g., RGB565, RGB888).
     ///
     /// This updates the display controller's interface pixel format setting to match the data being
@@ -118,10 +196,10 @@ pub trait Panel<B: DisplayBus> {
     ) -> Result<(), DisplayError<B::Error>>;
 }
 
-
 /// An optional trait for setting the panel’s own brightness via commands.
 ///
-/// Note: Using a PWM pin to implement this trait is not recommended.
+/// Note: Using a PWM pin to implement this trait is not recommended, use
+/// [`PwmBacklight`](backlight::PwmBacklight) instead.
 #[allow(async_fn_in_trait)]
 pub trait PanelSetBrightness<B: DisplayBus>: Panel<B> {
     /// Sets the panel’s own brightness.
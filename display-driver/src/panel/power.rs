@@ -0,0 +1,71 @@
+use embedded_hal::digital::{Error, OutputPin};
+
+use crate::DisplayError;
+
+use super::reset::NoResetPin;
+
+/// Dummy pin implementation for lines not controlled by the driver, e.g. the default power-supply
+/// and backlight pins.
+pub type NoPin = NoResetPin;
+
+/// Option for an LCD enable line, such as a power-supply enable or a backlight enable pin.
+///
+/// This follows the same active-level convention as [`LCDResetOption`](super::reset::LCDResetOption):
+/// the variant names the level that *enables* the line.
+pub enum LCDEnableOption<P: OutputPin> {
+    /// Enabled by driving the GPIO pin high.
+    PinHigh(P),
+    /// Enabled by driving the GPIO pin low.
+    PinLow(P),
+    /// Not controlled by the driver.
+    None,
+}
+
+impl<P: OutputPin> LCDEnableOption<P> {
+    /// Creates a new PinHigh enable option.
+    pub fn new_pin(pin: P) -> Self {
+        Self::PinHigh(pin)
+    }
+
+    /// Creates a new enable option with specified active level.
+    pub fn new_pin_with_level(pin: P, enable_level: bool) -> Self {
+        if enable_level {
+            Self::PinHigh(pin)
+        } else {
+            Self::PinLow(pin)
+        }
+    }
+
+    /// Releases the pin if held.
+    pub fn release(self) -> Option<P> {
+        match self {
+            Self::PinHigh(pin) => Some(pin),
+            Self::PinLow(pin) => Some(pin),
+            Self::None => None,
+        }
+    }
+
+    /// Returns `true` if the line is not controlled by the driver.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// Drives the line to its enabled or disabled level.
+    ///
+    /// Does nothing for [`LCDEnableOption::None`].
+    pub fn set_enabled<E>(&mut self, enabled: bool) -> Result<(), DisplayError<E>> {
+        let result = match self {
+            Self::PinHigh(pin) => pin.set_state(enabled.into()),
+            Self::PinLow(pin) => pin.set_state((!enabled).into()),
+            Self::None => return Ok(()),
+        };
+        result.map_err(|err| DisplayError::PinError(err.kind()))
+    }
+}
+
+impl LCDEnableOption<NoPin> {
+    /// Creates a None enable option.
+    pub fn none() -> Self {
+        Self::None
+    }
+}
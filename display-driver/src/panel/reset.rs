@@ -96,34 +96,36 @@ impl<'a, P: OutputPin, B: DisplayBus, D: DelayNs> LCDResetHandler<'a, P, B, D> {
     }
 
     /// Sets the reset state.
-    pub fn set_reset(&mut self, reset: bool) -> Result<(), B::Error> {
+    ///
+    /// Pin errors are reported as [`DisplayError::PinError`]. If the bus cannot drive a reset line,
+    /// [`DisplayError::ResetUnsupported`] is returned.
+    pub fn set_reset(&mut self, reset: bool) -> Result<(), DisplayError<B::Error>> {
         match *self.option {
             LCDResetOption::PinHigh(ref mut pin) => {
                 if reset {
-                    pin.set_high().map_err(|_| unreachable!())
+                    pin.set_high().map_err(pin_error)
                 } else {
-                    pin.set_low().map_err(|_| unreachable!())
+                    pin.set_low().map_err(pin_error)
                 }
             }
             LCDResetOption::PinLow(ref mut pin) => {
                 if reset {
-                    pin.set_low().map_err(|_| unreachable!())
+                    pin.set_low().map_err(pin_error)
                 } else {
-                    pin.set_high().map_err(|_| unreachable!())
+                    pin.set_high().map_err(pin_error)
                 }
             }
             LCDResetOption::Bus => self.bus.set_reset(reset).map_err(|err| match err {
-                DisplayError::BusError(e) => e,
-                DisplayError::Unsupported => panic!("Bus cannot reset"),
-                _ => unreachable!(),
+                DisplayError::Unsupported => DisplayError::ResetUnsupported,
+                err => err,
             }),
             LCDResetOption::Software => Ok(()),
-            LCDResetOption::None => unreachable!(),
+            LCDResetOption::None => Ok(()),
         }
     }
 
     /// Performs the reset sequence: assert -> wait -> release -> wait.
-    pub async fn reset(&mut self) -> Result<(), B::Error> {
+    pub async fn reset(&mut self) -> Result<(), DisplayError<B::Error>> {
         if matches!(self.option, LCDResetOption::Software) {
             if let Some(cmd) = self.software_reset_cmd {
                 self.bus.write_cmd(cmd).await?;
@@ -144,6 +146,10 @@ impl<'a, P: OutputPin, B: DisplayBus, D: DelayNs> LCDResetHandler<'a, P, B, D> {
     }
 }
 
+fn pin_error<E: embedded_hal::digital::Error, BE>(err: E) -> DisplayError<BE> {
+    DisplayError::PinError(err.kind())
+}
+
 /// Dummy pin implementation for when no reset pin is used.
 pub struct NoResetPin {}
 impl embedded_hal::digital::ErrorType for NoResetPin {
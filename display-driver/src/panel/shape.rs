@@ -0,0 +1,133 @@
+/// The visible shape of the display glass.
+///
+/// Round and rounded-corner panels still have a rectangular frame memory, but some of its pixels
+/// are never visible. The shape is evaluated against the current (oriented) display size, so it
+/// has to be symmetric under rotation, which holds for all variants.
+///
+/// It is used by [`FrameBufferedDisplayDriver::flush_visible`](crate::eg::FrameBufferedDisplayDriver::flush_visible)
+/// to skip the invisible pixels, and by [`ShapeClip`](crate::eg::ShapeClip) to skip drawing them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VisibleShape {
+    /// The whole rectangle is visible (default).
+    #[default]
+    Rectangle,
+    /// A circle inscribed in the display rectangle, centered.
+    Circle,
+    /// A rectangle with rounded corners of the given radius in pixels.
+    RoundedRect { radius: u16 },
+}
+
+impl VisibleShape {
+    /// Returns the visible columns `(x0, x1)` (inclusive) of row `y` on a `width` x `height`
+    /// display, or `None` if no pixel of the row is visible.
+    pub fn span(&self, y: u16, width: u16, height: u16) -> Option<(u16, u16)> {
+        if width == 0 || y >= height {
+            return None;
+        }
+
+        // Distances are computed in half pixels, so pixel centers are at odd coordinates.
+        let (w, h, y) = (width as i32, height as i32, y as i32);
+        match *self {
+            VisibleShape::Rectangle => Some((0, width - 1)),
+            VisibleShape::Circle => {
+                let diameter = w.min(h);
+                let dy = 2 * y + 1 - h;
+                let half = isqrt_diff(diameter, dy)?;
+                Self::clamp_span((w - half).div_euclid(2), (w - 1 + half).div_euclid(2), w)
+            }
+            VisibleShape::RoundedRect { radius } => {
+                let r = (radius as i32).min(w / 2).min(h / 2);
+                // Distance from the corner circle's center row, mirrored for the bottom corners
+                let row = y.min(h - 1 - y);
+                if row >= r {
+                    return Some((0, width - 1));
+                }
+                let dy = 2 * r - (2 * row + 1);
+                let half = isqrt_diff(2 * r, dy)?;
+                let x0 = (2 * r - half).div_euclid(2);
+                Self::clamp_span(x0, w - 1 - x0, w)
+            }
+        }
+    }
+
+    /// Returns whether the pixel `(x, y)` is visible on a `width` x `height` display.
+    pub fn contains(&self, x: u16, y: u16, width: u16, height: u16) -> bool {
+        matches!(self.span(y, width, height), Some((x0, x1)) if x >= x0 && x <= x1)
+    }
+
+    /// Returns the number of visible pixels on a `width` x `height` display.
+    pub fn visible_pixels(&self, width: u16, height: u16) -> usize {
+        (0..height)
+            .filter_map(|y| self.span(y, width, height))
+            .map(|(x0, x1)| (x1 - x0 + 1) as usize)
+            .sum()
+    }
+
+    fn clamp_span(x0: i32, x1: i32, w: i32) -> Option<(u16, u16)> {
+        let (x0, x1) = (x0.max(0), x1.min(w - 1));
+        (x0 <= x1).then_some((x0 as u16, x1 as u16))
+    }
+}
+
+/// Returns `sqrt(r² - d²)`, or `None` if `|d| > r`.
+fn isqrt_diff(r: i32, d: i32) -> Option<i32> {
+    let sq = r * r - d * d;
+    (sq >= 0).then(|| (sq as u32).isqrt() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans<const H: usize>(shape: VisibleShape, width: u16) -> [Option<(u16, u16)>; H] {
+        core::array::from_fn(|y| shape.span(y as u16, width, H as u16))
+    }
+
+    #[test]
+    fn rectangle_span() {
+        assert_eq!(spans::<3>(VisibleShape::Rectangle, 5), [Some((0, 4)); 3]);
+        assert_eq!(VisibleShape::Rectangle.span(3, 5, 3), None);
+        assert_eq!(VisibleShape::Rectangle.span(0, 0, 3), None);
+    }
+
+    #[test]
+    fn circle_span() {
+        let full = Some((0, 7));
+        assert_eq!(
+            spans::<8>(VisibleShape::Circle, 8),
+            [
+                Some((2, 5)),
+                Some((1, 6)),
+                full,
+                full,
+                full,
+                full,
+                Some((1, 6)),
+                Some((2, 5))
+            ]
+        );
+        // Inscribed in the shorter side, centered horizontally
+        let wide = Some((2, 7));
+        assert_eq!(
+            spans::<6>(VisibleShape::Circle, 10),
+            [Some((3, 6)), wide, wide, wide, wide, Some((3, 6))]
+        );
+        assert_eq!(VisibleShape::Circle.visible_pixels(8, 8), 52);
+    }
+
+    #[test]
+    fn rounded_rect_span() {
+        let shape = VisibleShape::RoundedRect { radius: 2 };
+        let full = Some((0, 9));
+        assert_eq!(
+            spans::<6>(shape, 10),
+            [Some((1, 8)), full, full, full, full, Some((1, 8))]
+        );
+        assert!(!shape.contains(0, 0, 10, 6));
+        assert!(shape.contains(1, 0, 10, 6));
+        assert_eq!(
+            spans::<4>(VisibleShape::RoundedRect { radius: 0 }, 10),
+            spans::<4>(VisibleShape::Rectangle, 10)
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Mock bus and panel for the unit tests.
+
+extern crate std;
+
+use core::{
+    convert::Infallible,
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+use std::vec::Vec;
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::{
+    bus::{BusBytesIo, BusHardwareFill, DisplayBus, ErrorType, Metadata},
+    Area, ColorFormat, DisplayError, Panel, SolidColor,
+};
+
+/// Runs `future` to completion. The mocks never wait, so it is polled in a loop.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// A pixel data transfer recorded by [`MockBus`].
+#[derive(Debug, Clone)]
+pub(crate) struct PixelWrite {
+    pub data: Vec<u8>,
+    pub metadata: Metadata,
+}
+
+/// A bus recording everything written to it.
+///
+/// `SWAP` sets [`DisplayBus::HARDWARE_BYTE_SWAP`].
+#[derive(Default)]
+pub(crate) struct MockBus<const SWAP: bool = false> {
+    /// Commands, each followed by its parameters.
+    pub cmds: Vec<Vec<u8>>,
+    pub pixel_writes: Vec<PixelWrite>,
+    pub fills: Vec<(SolidColor, Area)>,
+    /// Bytes written with [`BusBytesIo::write_data_bytes`].
+    pub data: Vec<u8>,
+}
+
+impl<const SWAP: bool> MockBus<SWAP> {
+    /// Returns the pixel data of all `write_pixels` calls.
+    pub fn pixels(&self) -> Vec<u8> {
+        self.pixel_writes
+            .iter()
+            .flat_map(|write| write.data.iter().copied())
+            .collect()
+    }
+}
+
+impl<const SWAP: bool> ErrorType for MockBus<SWAP> {
+    type Error = Infallible;
+}
+
+impl<const SWAP: bool> DisplayBus for MockBus<SWAP> {
+    const HARDWARE_BYTE_SWAP: bool = SWAP;
+
+    async fn write_cmd(&mut self, cmd: &[u8]) -> Result<(), Self::Error> {
+        self.cmds.push(cmd.to_vec());
+        Ok(())
+    }
+
+    async fn write_cmd_with_params(
+        &mut self,
+        cmd: &[u8],
+        params: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.cmds.push([cmd, params].concat());
+        Ok(())
+    }
+
+    async fn write_pixels(
+        &mut self,
+        _cmd: &[u8],
+        data: &[u8],
+        metadata: Metadata,
+    ) -> Result<(), DisplayError<Self::Error>> {
+        self.pixel_writes.push(PixelWrite {
+            data: data.to_vec(),
+            metadata,
+        });
+        Ok(())
+    }
+}
+
+impl<const SWAP: bool> BusHardwareFill for MockBus<SWAP> {
+    async fn fill_solid(
+        &mut self,
+        _cmd: &[u8],
+        color: SolidColor,
+        area: Area,
+    ) -> Result<(), DisplayError<Self::Error>> {
+        self.fills.push((color, area));
+        Ok(())
+    }
+}
+
+impl<const SWAP: bool> BusBytesIo for MockBus<SWAP> {
+    async fn write_cmd_bytes(&mut self, cmd: &[u8]) -> Result<(), Self::Error> {
+        self.cmds.push(cmd.to_vec());
+        Ok(())
+    }
+
+    async fn write_data_bytes(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.data.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+/// A panel of `width` x `height` pixels, sending only the window and the color format.
+pub(crate) struct MockPanel {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl MockPanel {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self { width, height }
+    }
+}
+
+impl<B: DisplayBus> Panel<B> for MockPanel {
+    const CMD_LEN: usize = 1;
+    const PIXEL_WRITE_CMD: [u8; 4] = [0x2C, 0, 0, 0];
+
+    fn width(&self) -> u16 {
+        self.width
+    }
+
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    async fn init<D: DelayNs>(
+        &mut self,
+        _bus: &mut B,
+        _delay: D,
+    ) -> Result<(), DisplayError<B::Error>> {
+        Ok(())
+    }
+
+    async fn set_window(
+        &mut self,
+        bus: &mut B,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let [x0, x1, y0, y1] = [x0, x1, y0, y1].map(|v| v as u8);
+        bus.write_cmd_with_params(&[0x2A], &[x0, x1]).await?;
+        bus.write_cmd_with_params(&[0x2B], &[y0, y1]).await?;
+        Ok(())
+    }
+
+    async fn set_display_on(
+        &mut self,
+        _bus: &mut B,
+        _on: bool,
+    ) -> Result<(), DisplayError<B::Error>> {
+        Ok(())
+    }
+
+    async fn set_color_format(
+        &mut self,
+        bus: &mut B,
+        color_format: ColorFormat,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[0x3A], &[color_format.size_bits()])
+            .await?;
+        Ok(())
+    }
+}
+
+/// A delay returning immediately.
+pub(crate) struct NoDelay;
+
+impl DelayNs for NoDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
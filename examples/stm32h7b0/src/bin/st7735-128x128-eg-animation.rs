@@ -226,7 +226,7 @@ async fn main(_spawner: Spawner) {
     let fb = FB.init(Framebuffer::new());
 
     // Create the buffered display wrapper taking ownership of disp
-    let mut fb_display = FrameBufferedDisplayDriver::new(disp, fb);
+    let mut fb_display = FrameBufferedDisplayDriver::new(disp, fb).unwrap();
 
     // Animation loop with FPS control
     let mut frame: u32 = 0;
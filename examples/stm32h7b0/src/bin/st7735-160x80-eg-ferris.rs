@@ -93,7 +93,7 @@ async fn main(_spawner: Spawner) {
     let fb = FB.init(Framebuffer::new());
 
     // Create the buffered display wrapper taking ownership of disp
-    let mut fb_display = FrameBufferedDisplayDriver::new(disp, fb);
+    let mut fb_display = FrameBufferedDisplayDriver::new(disp, fb).unwrap();
     fb_display.clear(Rgb565::BLACK).unwrap();
 
     // Draw L-shaped markers at the corners to verify offsets
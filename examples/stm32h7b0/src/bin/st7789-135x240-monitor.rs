@@ -109,7 +109,7 @@ async fn main(_spawner: Spawner) {
     let fb = FB.init(Framebuffer::new());
 
     // Create the buffered display wrapper taking ownership of disp
-    let mut fb_display = FrameBufferedDisplayDriver::new(disp, fb);
+    let mut fb_display = FrameBufferedDisplayDriver::new(disp, fb).unwrap();
 
     draw_ui(&mut fb_display);
 
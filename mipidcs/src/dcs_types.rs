@@ -1,7 +1,7 @@
-use core::mem;
-
 use bitflags::bitflags;
-use display_driver::Orientation;
+use display_driver::{Mirror, Orientation};
+
+use crate::PanelSpec;
 
 /// Gamma Curve selection (Command 0x26).
 #[repr(u8)]
@@ -21,7 +21,10 @@ impl GammaSet {
     /// Create a GammaSet from a generic integer index (0-3).
     pub fn gc(n: u8) -> Option<Self> {
         match n {
-            0..=3 => unsafe { mem::transmute(1u8 << n) },
+            0 => Some(Self::GC0),
+            1 => Some(Self::GC1),
+            2 => Some(Self::GC2),
+            3 => Some(Self::GC3),
             _ => None,
         }
     }
@@ -186,6 +189,62 @@ impl AddressMode {
         Self::from_bits_retain(bits)
     }
 
+    /// Returns the Address Mode of panel `S` after initialization: its mounting
+    /// ([`PanelSpec::MOUNT_ORIENTATION`], [`PanelSpec::MOUNT_MIRROR`]) and BGR order.
+    pub const fn initial<S: PanelSpec>() -> Self {
+        let mut bits = Self::from_orientation_mirror(S::MOUNT_ORIENTATION, S::MOUNT_MIRROR).bits();
+        if S::BGR {
+            bits |= Self::BGR.bits();
+        }
+        Self::from_bits_retain(bits)
+    }
+
+    /// Applies the MV, MX and MY flags of `inner` in the space set up by the ones of `self`, e.g.
+    /// the application orientation on top of the mounting orientation. The other flags are kept
+    /// from `self`.
+    pub const fn compose(&self, inner: AddressMode) -> Self {
+        let mask_bits = Self::MV.bits() | Self::MX.bits() | Self::MY.bits();
+        let swapped = self.contains(Self::MV);
+        // Swapping first exchanges the axes the inner mirrors apply to.
+        let (inner_mx, inner_my) = if swapped {
+            (inner.contains(Self::MY), inner.contains(Self::MX))
+        } else {
+            (inner.contains(Self::MX), inner.contains(Self::MY))
+        };
+        let inner_bits = Self::new_simple(inner_mx, inner_my, inner.contains(Self::MV), false);
+        Self::from_bits_retain(self.bits() ^ (inner_bits.bits() & mask_bits))
+    }
+
+    /// Returns the MV, MX and MY flags that undo the ones of `self`, so that
+    /// `mode.inverse().compose(mode.compose(inner))` has the flags of `inner`. The other flags are
+    /// kept.
+    pub const fn inverse(&self) -> Self {
+        if !self.contains(Self::MV) {
+            return *self;
+        }
+        // Transposing maps each mirror onto the other axis.
+        let mirrors = Self::MX.bits() | Self::MY.bits();
+        let bits = self.bits() & !mirrors;
+        let mx = self.contains(Self::MX);
+        let my = self.contains(Self::MY);
+        Self::from_bits_retain(bits | Self::new_simple(my, mx, false, false).bits())
+    }
+
+    /// Creates the MV, MX and MY flags of `orientation` with `mirror` applied on top.
+    pub const fn from_orientation_mirror(orientation: Orientation, mirror: Mirror) -> Self {
+        let bits = Self::from_orientation(orientation).bits();
+        let swapped = bits & Self::MV.bits() != 0;
+        // Mirroring a logical axis flips the physical axis it is mapped to.
+        let flip = match (mirror, swapped) {
+            (Mirror::None, _) => 0,
+            (Mirror::Horizontal, false) | (Mirror::Vertical, true) => Self::MX.bits(),
+            (Mirror::Horizontal, true) | (Mirror::Vertical, false) => Self::MY.bits(),
+        };
+        Self::from_bits_retain(bits ^ flip)
+    }
+
+    /// Returns the orientation, or `None` for mirrored combinations, see
+    /// [`orientation_mirror`](Self::orientation_mirror).
     pub const fn orientation(&self) -> Option<Orientation> {
         let bits = self.bits();
         match bits & (Self::MV.bits() | Self::MX.bits() | Self::MY.bits()) {
@@ -197,8 +256,35 @@ impl AddressMode {
         }
     }
 
+    /// Returns the orientation and mirroring of the MV, MX and MY flags.
+    ///
+    /// All eight combinations are mapped. Mirrored transposed modes can be expressed in two ways
+    /// and are returned based on [`Orientation::Deg90`], e.g. MV alone as `Deg90` mirrored
+    /// vertically, which is the same as `Deg270` mirrored horizontally.
+    pub const fn orientation_mirror(&self) -> (Orientation, Mirror) {
+        let mv = self.contains(Self::MV);
+        let mx = self.contains(Self::MX);
+        let my = self.contains(Self::MY);
+        match (mv, mx, my) {
+            (false, false, false) => (Orientation::Deg0, Mirror::None),
+            (false, true, false) => (Orientation::Deg0, Mirror::Horizontal),
+            (false, false, true) => (Orientation::Deg0, Mirror::Vertical),
+            (false, true, true) => (Orientation::Deg180, Mirror::None),
+            (true, true, false) => (Orientation::Deg90, Mirror::None),
+            (true, false, true) => (Orientation::Deg270, Mirror::None),
+            (true, false, false) => (Orientation::Deg90, Mirror::Vertical),
+            (true, true, true) => (Orientation::Deg90, Mirror::Horizontal),
+        }
+    }
+
     pub fn set_orientation(&mut self, orientation: Orientation) {
-        let bits = Self::from_orientation(orientation).bits();
+        self.set_orientation_mirror(orientation, Mirror::None);
+    }
+
+    /// Sets the MV, MX and MY flags to `orientation` with `mirror` applied on top, keeping the
+    /// other flags.
+    pub fn set_orientation_mirror(&mut self, orientation: Orientation, mirror: Mirror) {
+        let bits = Self::from_orientation_mirror(orientation, mirror).bits();
         let mask_bits = Self::MV.bits() | Self::MX.bits() | Self::MY.bits();
         *self = Self::from_bits_retain((self.bits() & !mask_bits) | (bits & mask_bits));
     }
@@ -211,17 +297,13 @@ impl AddressRange {
     pub const fn new(start: u16, end: u16) -> Self {
         let s = start.to_be_bytes();
         let e = end.to_be_bytes();
-        Self {
-            0: [s[0], s[1], e[0], e[1]],
-        }
+        Self([s[0], s[1], e[0], e[1]])
     }
 
     pub const fn new_with_offset(start: u16, end: u16, offset: u16) -> Self {
         let s = (start + offset).to_be_bytes();
         let e = (end + offset).to_be_bytes();
-        Self {
-            0: [s[0], s[1], e[0], e[1]],
-        }
+        Self([s[0], s[1], e[0], e[1]])
     }
 
     pub const fn as_bytes(&self) -> &[u8; 4] {
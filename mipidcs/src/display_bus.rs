@@ -1,5 +1,8 @@
 use display_driver::bus::DisplayBus;
-use display_driver::panel::{initseq::sequenced_init, reset::LCDResetHandler, Orientation, Panel};
+use display_driver::panel::{
+    initseq::sequenced_init, reset::LCDResetHandler, shape::VisibleShape, Mirror, Orientation,
+    Panel,
+};
 
 use display_driver::{ColorFormat, DisplayError};
 use embedded_hal::digital::OutputPin;
@@ -17,6 +20,8 @@ where
 {
     const CMD_LEN: usize = 1;
     const PIXEL_WRITE_CMD: [u8; 4] = [WRITE_MEMORY_START, 0, 0, 0];
+    const DISPLAY_ON_DELAY_MS: u8 = 20;
+    const POWER_OFF_DELAY_MS: u8 = S::POWER_OFF_DELAY_MS;
 
     fn width(&self) -> u16 {
         if self.address_mode.is_xy_swapped() {
@@ -42,7 +47,15 @@ where
         }
     }
 
-    async fn init<D: DelayNs>(&mut self, bus: &mut B, mut delay: D) -> Result<(), B::Error> {
+    fn visible_shape(&self) -> VisibleShape {
+        S::VISIBLE_SHAPE
+    }
+
+    async fn init<D: DelayNs>(
+        &mut self,
+        bus: &mut B,
+        mut delay: D,
+    ) -> Result<(), DisplayError<B::Error>> {
         // Hardware Reset
         let mut reseter = LCDResetHandler::new(
             &mut self.reset_pin,
@@ -53,8 +66,11 @@ where
             Some(&[SOFT_RESET]),
         );
         reseter.reset().await?;
+        self.reset_address_mode();
 
-        sequenced_init(Self::INIT_STEPS.into_iter(), &mut delay, bus).await
+        sequenced_init(Self::INIT_STEPS.into_iter(), &mut delay, bus)
+            .await
+            .map_err(DisplayError::BusError)
     }
 
     async fn set_window(
@@ -75,10 +91,16 @@ where
         bus: &mut B,
         color_format: ColorFormat,
     ) -> Result<(), DisplayError<B::Error>> {
-        let bits = color_format.size_bits();
-
-        // Use from_bit_count as requested
-        let pf_type = PixelFormatType::from_bit_count(bits).ok_or(DisplayError::Unsupported)?;
+        let pf_type = match color_format {
+            ColorFormat::RGB332 => PixelFormatType::Bits8,
+            ColorFormat::RGB444 => PixelFormatType::Bits12,
+            ColorFormat::RGB565 => PixelFormatType::Bits16,
+            ColorFormat::RGB666 => PixelFormatType::Bits18,
+            ColorFormat::RGB888 => PixelFormatType::Bits24,
+            ColorFormat::Binary | ColorFormat::Gray2 | ColorFormat::Gray4 | ColorFormat::Gray8 => {
+                return Err(DisplayError::Unsupported)
+            }
+        };
 
         // Use dbi_and_dpi for better compatibility
         let pf = PixelFormat::dbi_and_dpi(pf_type);
@@ -88,17 +110,55 @@ where
             .map_err(DisplayError::BusError)
     }
 
+    async fn set_display_on(
+        &mut self,
+        bus: &mut B,
+        on: bool,
+    ) -> Result<(), DisplayError<B::Error>> {
+        match on {
+            true => Self::set_display_on(self, bus).await,
+            false => Self::set_display_off(self, bus).await,
+        }
+        .map_err(DisplayError::BusError)
+    }
+
+    async fn set_sleep<D: DelayNs>(
+        &mut self,
+        bus: &mut B,
+        sleep: bool,
+        mut delay: D,
+    ) -> Result<(), DisplayError<B::Error>> {
+        if sleep {
+            self.enter_sleep_mode(bus).await?;
+            delay.delay_ms(S::SLEEP_IN_DELAY_MS as u32).await;
+        } else {
+            self.exit_sleep_mode(bus).await?;
+            delay.delay_ms(S::SLEEP_OUT_DELAY_MS as u32).await;
+        }
+        Ok(())
+    }
+
     async fn set_orientation(
         &mut self,
         bus: &mut B,
         orientation: Orientation,
     ) -> Result<(), DisplayError<B::Error>> {
-        let mut mode = self.address_mode;
-
-        mode.set_orientation(orientation);
+        let mode = self.mounted_address_mode(orientation, self.mirror);
 
         self.set_address_mode(bus, mode, Some(orientation))
             .await
             .map_err(DisplayError::BusError)
     }
+
+    async fn set_mirror(
+        &mut self,
+        bus: &mut B,
+        mirror: Mirror,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let mode = self.mounted_address_mode(self.orientation, mirror);
+
+        self.set_address_mode(bus, mode, Some(self.orientation))
+            .await
+            .map_err(DisplayError::BusError)
+    }
 }
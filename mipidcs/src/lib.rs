@@ -3,14 +3,18 @@
 pub mod consts;
 pub mod dcs_types;
 pub mod display_bus;
+pub mod transform;
 
 use core::marker::PhantomData;
 use display_driver::bus::DisplayBus;
-use display_driver::panel::{initseq::InitStep, reset::LCDResetOption, Orientation};
+use display_driver::panel::{
+    initseq::InitStep, reset::LCDResetOption, shape::VisibleShape, Mirror, Orientation,
+};
 use embedded_hal::digital::OutputPin;
 
 pub use crate::consts::*;
 pub use crate::dcs_types::*;
+pub use crate::transform::Transform;
 
 /// A generic driver for MIPI DCS compliant displays.
 ///
@@ -27,6 +31,8 @@ where
     /// The current Address Mode (MADCTL) setting.
     pub address_mode: AddressMode,
     pub orientation: Orientation,
+    /// The current mirroring, applied on top of the orientation.
+    pub mirror: Mirror,
     _phantom: PhantomData<(B, S)>,
 }
 
@@ -38,31 +44,45 @@ where
 {
     /// Creates a new generic MIPI DCS driver.
     pub fn new(reset_pin: LCDResetOption<RST>) -> Self {
-        let mut address_mode = AddressMode::empty();
-        address_mode.set(AddressMode::BGR, S::BGR);
         Self {
             reset_pin,
-            address_mode,
+            address_mode: AddressMode::initial::<S>(),
             orientation: Orientation::Deg0,
+            mirror: Mirror::None,
             _phantom: PhantomData,
         }
     }
 
-    /// Returns the column (X) and page (Y) offsets based on the current orientation
+    /// Returns the [`Transform`] of the current Address Mode, mapping between physical and
+    /// logical coordinates, e.g. for touch input.
+    pub fn transform(&self) -> Transform {
+        Transform::for_spec::<S>(self.address_mode)
+    }
+
+    /// Returns the current Address Mode with the application `orientation` and `mirror`,
+    /// composed with the mounting of the panel.
+    pub fn mounted_address_mode(&self, orientation: Orientation, mirror: Mirror) -> AddressMode {
+        let mount = AddressMode::from_orientation_mirror(S::MOUNT_ORIENTATION, S::MOUNT_MIRROR);
+        let mut mode = self.address_mode;
+        mode.set_orientation_mirror(Orientation::Deg0, Mirror::None);
+        let flags = mount.compose(AddressMode::from_orientation_mirror(orientation, mirror));
+        mode | flags
+    }
+
+    /// Resets the Address Mode state to [`AddressMode::initial`], the one sent by the
+    /// initialization sequence, with the application orientation and mirroring cleared.
+    ///
+    /// Panels call this on (re)initialization, as the reset leaves the previous state stale.
+    pub fn reset_address_mode(&mut self) {
+        self.address_mode = AddressMode::initial::<S>();
+        self.orientation = Orientation::Deg0;
+        self.mirror = Mirror::None;
+    }
+
+    /// Returns the column (X) and page (Y) offsets based on the current Address Mode
     /// and the `INVERT_TRANSPOSED_OFFSET` setting.
     pub fn get_offset(&self) -> (u16, u16) {
-        match (self.orientation, S::INVERT_TRANSPOSED_OFFSET) {
-            (Orientation::Deg0, _) => (S::PHYSICAL_X_OFFSET, S::PHYSICAL_Y_OFFSET),
-            (Orientation::Deg180, _) => {
-                (S::PHYSICAL_X_OFFSET_ROTATED, S::PHYSICAL_Y_OFFSET_ROTATED)
-            }
-            (Orientation::Deg90, false) | (Orientation::Deg270, true) => {
-                (S::PHYSICAL_Y_OFFSET, S::PHYSICAL_X_OFFSET)
-            }
-            (Orientation::Deg270, false) | (Orientation::Deg90, true) => {
-                (S::PHYSICAL_Y_OFFSET_ROTATED, S::PHYSICAL_X_OFFSET_ROTATED)
-            }
-        }
+        self.transform().offset::<S>()
     }
 
     /// Software reset on the display controller (Command 0x01).
@@ -90,6 +110,61 @@ where
         bus.write_cmd(&[SET_DISPLAY_ON]).await
     }
 
+    /// Enter Partial Mode (Command 0x12).
+    ///
+    /// Only the rows set by [`set_partial_rows`](Self::set_partial_rows) are displayed.
+    pub async fn enter_partial_mode(&self, bus: &mut B) -> Result<(), B::Error> {
+        bus.write_cmd(&[ENTER_PARTIAL_MODE]).await
+    }
+
+    /// Enter Normal Mode, leaving Partial Mode (Command 0x13).
+    pub async fn enter_normal_mode(&self, bus: &mut B) -> Result<(), B::Error> {
+        bus.write_cmd(&[ENTER_NORMAL_MODE]).await
+    }
+
+    /// Set the Partial Mode rows (Command 0x30).
+    ///
+    /// The rows are frame memory rows (inclusive), they are not affected by the orientation.
+    pub async fn set_partial_rows(
+        &self,
+        bus: &mut B,
+        start: u16,
+        end: u16,
+    ) -> Result<(), B::Error> {
+        bus.write_cmd_with_params(
+            &[SET_PARTIAL_ROWS],
+            AddressRange::new(start, end).as_bytes(),
+        )
+        .await
+    }
+
+    /// Set the Partial Mode columns (Command 0x31).
+    ///
+    /// The columns are frame memory columns (inclusive), they are not affected by the orientation.
+    /// Not all controllers support partial columns.
+    pub async fn set_partial_columns(
+        &self,
+        bus: &mut B,
+        start: u16,
+        end: u16,
+    ) -> Result<(), B::Error> {
+        bus.write_cmd_with_params(
+            &[SET_PARTIAL_COLUMNS],
+            AddressRange::new(start, end).as_bytes(),
+        )
+        .await
+    }
+
+    /// Set Idle Mode (Command 0x38 / 0x39).
+    ///
+    /// `true` enters Idle Mode (0x39, reduced 8-color depth), `false` exits Idle Mode (0x38).
+    pub async fn set_idle_mode(&self, bus: &mut B, idle: bool) -> Result<(), B::Error> {
+        match idle {
+            true => bus.write_cmd(&[ENTER_IDLE_MODE]).await,
+            false => bus.write_cmd(&[EXIT_IDLE_MODE]).await,
+        }
+    }
+
     /// Set the column address window (Command 0x2A).
     pub async fn set_column_address(
         &self,
@@ -144,7 +219,12 @@ where
     /// * `bus` - The display bus to write to.
     /// * `mode` - The new address mode to set.
     /// * `orientation_if_changed` - Set the orientation in state machine if it has changed
-    /// by your self for correct offset handling.
+    ///   by your self. The offsets follow the MV, MX and MY flags of `mode`.
+    ///
+    /// The orientation and mirroring state are derived from the MV, MX and MY flags of `mode`,
+    /// relative to the mounting of the panel. As some combinations can be described in more than
+    /// one way (e.g. `Deg180` mirrored horizontally is `Deg0` mirrored vertically),
+    /// `orientation_if_changed` is kept if it can describe `mode` with some mirroring.
     ///
     /// # Note
     ///
@@ -155,10 +235,19 @@ where
         mode: AddressMode,
         orientation_if_changed: Option<Orientation>,
     ) -> Result<(), B::Error> {
-        self.address_mode = mode;
+        let mount = AddressMode::from_orientation_mirror(S::MOUNT_ORIENTATION, S::MOUNT_MIRROR);
+        let flags =
+            mount.inverse().compose(mode) & (AddressMode::MV | AddressMode::MX | AddressMode::MY);
+        (self.orientation, self.mirror) = flags.orientation_mirror();
         if let Some(orientation) = orientation_if_changed {
-            self.orientation = orientation;
+            let mirror = [Mirror::None, Mirror::Horizontal, Mirror::Vertical]
+                .into_iter()
+                .find(|&mirror| AddressMode::from_orientation_mirror(orientation, mirror) == flags);
+            if let Some(mirror) = mirror {
+                (self.orientation, self.mirror) = (orientation, mirror);
+            }
         }
+        self.address_mode = mode;
         bus.write_cmd_with_params(&[SET_ADDRESS_MODE], &[mode.bits()])
             .await
     }
@@ -186,17 +275,12 @@ where
         }
     }
 
-    const INIT_STEPS: [InitStep<'_>; 6] = [
+    const INIT_STEPS: [InitStep<'_>; 4] = [
         InitStep::SingleCommand(EXIT_SLEEP_MODE),
-        InitStep::DelayMs(120),
+        InitStep::DelayMs(S::SLEEP_OUT_DELAY_MS),
         InitStep::select_cmd(S::INVERTED, ENTER_INVERT_MODE, EXIT_INVERT_MODE),
-        InitStep::CommandWithParams(
-            SET_ADDRESS_MODE,
-            &[if S::BGR { AddressMode::BGR.bits() } else { 0u8 }],
-        ),
-        // Power On
-        InitStep::SingleCommand(SET_DISPLAY_ON),
-        InitStep::DelayMs(20),
+        InitStep::CommandWithParams(SET_ADDRESS_MODE, &[AddressMode::initial::<S>().bits()]),
+        // Display On is sent later by the driver, after the frame memory has been prepared.
     ];
 }
 
@@ -239,9 +323,33 @@ pub trait PanelSpec {
     /// but in practice `false` appears to be the more common case.
     const INVERT_TRANSPOSED_OFFSET: bool = false;
 
+    /// How the panel is mounted in the product (default [`Orientation::Deg0`]).
+    ///
+    /// It is composed with the orientation set by the application, including the offsets, so the
+    /// application only deals with logical orientations. E.g. `Deg180` for a module mounted
+    /// upside down.
+    const MOUNT_ORIENTATION: Orientation = Orientation::Deg0;
+
+    /// Mirroring of the mounting, applied on top of `MOUNT_ORIENTATION` (default
+    /// [`Mirror::None`]).
+    const MOUNT_MIRROR: Mirror = Mirror::None;
+
     /// Whether the display is inverted (default false).
     const INVERTED: bool = false;
 
     /// Whether the display is BGR (default false).
     const BGR: bool = false;
+
+    /// The visible shape of the display glass, e.g. [`VisibleShape::Circle`] for round panels
+    /// (default [`VisibleShape::Rectangle`]).
+    const VISIBLE_SHAPE: VisibleShape = VisibleShape::Rectangle;
+
+    /// Time to wait after Sleep In (Command 0x10) in milliseconds (default 5).
+    const SLEEP_IN_DELAY_MS: u8 = 5;
+
+    /// Time to wait after Sleep Out (Command 0x11) in milliseconds (default 120).
+    const SLEEP_OUT_DELAY_MS: u8 = 120;
+
+    /// Time to wait between Sleep In and cutting the power supply in milliseconds (default 120).
+    const POWER_OFF_DELAY_MS: u8 = 120;
 }
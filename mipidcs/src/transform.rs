@@ -0,0 +1,272 @@
+use display_driver::{panel::Orientation, Area};
+
+use crate::{AddressMode, PanelSpec};
+
+/// Maps coordinates between the physical space of a panel and the logical space set by the
+/// Address Mode (MADCTL).
+///
+/// The physical space is the panel at [`Orientation::Deg0`] without mirroring, e.g. the space
+/// touch controllers mounted with the panel usually report in. The logical space is the one pixels
+/// are addressed in, after rotation and mirroring.
+///
+/// A logical point `(x, y)` is swapped to `(y, x)` if `swap_xy` (MV) is set, then mirrored along
+/// the physical columns if `mirror_x` (MX) and along the physical rows if `mirror_y` (MY). So
+/// [`Orientation::Deg90`] (MV, MX) turns the content clockwise.
+///
+/// Both spaces start at the first visible pixel: the address offsets of the panel are not
+/// included. To address the controller RAM, add [`offset`](Self::offset) to logical coordinates,
+/// as [`GenericMipidcs`](crate::GenericMipidcs) does when setting the window.
+///
+/// # Example
+/// ```ignore
+/// // Map a raw touch point to the current orientation
+/// let transform = display.panel.transform();
+/// let (x, y) = transform.to_logical(raw_x, raw_y);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transform {
+    /// Physical width in pixels.
+    pub width: u16,
+    /// Physical height in pixels.
+    pub height: u16,
+    /// Row/Column exchange (MV).
+    pub swap_xy: bool,
+    /// Column mirror (MX).
+    pub mirror_x: bool,
+    /// Row mirror (MY).
+    pub mirror_y: bool,
+}
+
+impl Transform {
+    /// Creates the transform of `mode` for a panel of `width` x `height` physical pixels.
+    ///
+    /// Only the MV, MX and MY flags are used.
+    pub const fn new(width: u16, height: u16, mode: AddressMode) -> Self {
+        Self {
+            width,
+            height,
+            swap_xy: mode.contains(AddressMode::MV),
+            mirror_x: mode.contains(AddressMode::MX),
+            mirror_y: mode.contains(AddressMode::MY),
+        }
+    }
+
+    /// Creates the transform of `orientation` for a panel of `width` x `height` physical pixels.
+    pub const fn from_orientation(width: u16, height: u16, orientation: Orientation) -> Self {
+        Self::new(width, height, AddressMode::from_orientation(orientation))
+    }
+
+    /// Creates the transform of `mode` for the panel described by `S`.
+    pub const fn for_spec<S: PanelSpec>(mode: AddressMode) -> Self {
+        Self::new(S::PHYSICAL_WIDTH, S::PHYSICAL_HEIGHT, mode)
+    }
+
+    /// Returns the MV, MX and MY flags of the transform.
+    pub const fn address_mode(&self) -> AddressMode {
+        AddressMode::new_simple(self.mirror_x, self.mirror_y, self.swap_xy, false)
+    }
+
+    /// Returns the size (width, height) of the logical space.
+    pub const fn logical_size(&self) -> (u16, u16) {
+        if self.swap_xy {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// Maps a logical point to the physical space.
+    ///
+    /// The point excludes the address offsets, see [`offset`](Self::offset). Points outside the logical space are clamped at the mirrored edges.
+    pub const fn to_physical(&self, x: u16, y: u16) -> (u16, u16) {
+        let (x, y) = if self.swap_xy { (y, x) } else { (x, y) };
+        (
+            mirror(x, self.width, self.mirror_x),
+            mirror(y, self.height, self.mirror_y),
+        )
+    }
+
+    /// Maps a physical point to the logical space, e.g. a raw touch point.
+    ///
+    /// Points outside the physical space are clamped at the mirrored edges.
+    pub const fn to_logical(&self, x: u16, y: u16) -> (u16, u16) {
+        let x = mirror(x, self.width, self.mirror_x);
+        let y = mirror(y, self.height, self.mirror_y);
+        if self.swap_xy {
+            (y, x)
+        } else {
+            (x, y)
+        }
+    }
+
+    /// Maps a logical area to the physical space.
+    ///
+    /// Like [`to_physical`](Self::to_physical), the area excludes the address offsets.
+    pub const fn area_to_physical(&self, area: Area) -> Area {
+        let (x0, y0) = self.to_physical(area.x, area.y);
+        let (x1, y1) = self.to_physical(
+            area.x + area.w.saturating_sub(1),
+            area.y + area.h.saturating_sub(1),
+        );
+        let (w, h) = if self.swap_xy {
+            (area.h, area.w)
+        } else {
+            (area.w, area.h)
+        };
+        Area::new(min(x0, x1), min(y0, y1), w, h)
+    }
+
+    /// Maps a physical area to the logical space.
+    pub const fn area_to_logical(&self, area: Area) -> Area {
+        let (x0, y0) = self.to_logical(area.x, area.y);
+        let (x1, y1) = self.to_logical(
+            area.x + area.w.saturating_sub(1),
+            area.y + area.h.saturating_sub(1),
+        );
+        let (w, h) = if self.swap_xy {
+            (area.h, area.w)
+        } else {
+            (area.w, area.h)
+        };
+        Area::new(min(x0, x1), min(y0, y1), w, h)
+    }
+
+    /// Returns the column (X) and page (Y) address offsets of the panel `S` in this transform.
+    ///
+    /// Mirroring an axis uses the `*_ROTATED` offset for it. For transposed modes, the X and Y
+    /// offsets are exchanged, and the `INVERT_TRANSPOSED_OFFSET` quirk selects which mirror flag
+    /// applies to which offset, see [`PanelSpec::INVERT_TRANSPOSED_OFFSET`].
+    ///
+    /// The offsets of the transposed modes which are not an orientation (MV alone, and MV with MX
+    /// and MY, used by mirrored orientations) have no reference table and follow the same rule.
+    /// They are derived, not checked on hardware.
+    pub const fn offset<S: PanelSpec>(&self) -> (u16, u16) {
+        const fn pick(rotated: bool, normal: u16, rotated_offset: u16) -> u16 {
+            if rotated {
+                rotated_offset
+            } else {
+                normal
+            }
+        }
+
+        if !self.swap_xy {
+            return (
+                pick(
+                    self.mirror_x,
+                    S::PHYSICAL_X_OFFSET,
+                    S::PHYSICAL_X_OFFSET_ROTATED,
+                ),
+                pick(
+                    self.mirror_y,
+                    S::PHYSICAL_Y_OFFSET,
+                    S::PHYSICAL_Y_OFFSET_ROTATED,
+                ),
+            );
+        }
+        let (x_rotated, y_rotated) = if S::INVERT_TRANSPOSED_OFFSET {
+            (self.mirror_x, self.mirror_x)
+        } else {
+            (self.mirror_y, !self.mirror_x)
+        };
+        (
+            pick(
+                x_rotated,
+                S::PHYSICAL_Y_OFFSET,
+                S::PHYSICAL_Y_OFFSET_ROTATED,
+            ),
+            pick(
+                y_rotated,
+                S::PHYSICAL_X_OFFSET,
+                S::PHYSICAL_X_OFFSET_ROTATED,
+            ),
+        )
+    }
+}
+
+/// Mirrors `v` in `0..size` if `mirrored`.
+const fn mirror(v: u16, size: u16, mirrored: bool) -> u16 {
+    if mirrored {
+        size.saturating_sub(1).saturating_sub(v)
+    } else {
+        v
+    }
+}
+
+const fn min(a: u16, b: u16) -> u16 {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Spec<const INVERT: bool>;
+
+    impl<const INVERT: bool> PanelSpec for Spec<INVERT> {
+        const PHYSICAL_WIDTH: u16 = 240;
+        const PHYSICAL_HEIGHT: u16 = 320;
+        const PHYSICAL_X_OFFSET: u16 = 2;
+        const PHYSICAL_Y_OFFSET: u16 = 1;
+        const PHYSICAL_X_OFFSET_ROTATED: u16 = 4;
+        const PHYSICAL_Y_OFFSET_ROTATED: u16 = 3;
+        const INVERT_TRANSPOSED_OFFSET: bool = INVERT;
+    }
+
+    /// The offsets of each orientation as originally selected by the driver.
+    fn expected<S: PanelSpec>(orientation: Orientation) -> (u16, u16) {
+        match (orientation, S::INVERT_TRANSPOSED_OFFSET) {
+            (Orientation::Deg0, _) => (S::PHYSICAL_X_OFFSET, S::PHYSICAL_Y_OFFSET),
+            (Orientation::Deg180, _) => {
+                (S::PHYSICAL_X_OFFSET_ROTATED, S::PHYSICAL_Y_OFFSET_ROTATED)
+            }
+            (Orientation::Deg90, false) | (Orientation::Deg270, true) => {
+                (S::PHYSICAL_Y_OFFSET, S::PHYSICAL_X_OFFSET)
+            }
+            (Orientation::Deg270, false) | (Orientation::Deg90, true) => {
+                (S::PHYSICAL_Y_OFFSET_ROTATED, S::PHYSICAL_X_OFFSET_ROTATED)
+            }
+        }
+    }
+
+    fn check<S: PanelSpec>() {
+        for (name, orientation) in [
+            ("Deg0", Orientation::Deg0),
+            ("Deg90", Orientation::Deg90),
+            ("Deg180", Orientation::Deg180),
+            ("Deg270", Orientation::Deg270),
+        ] {
+            let transform = Transform::from_orientation(240, 320, orientation);
+            assert_eq!(
+                transform.offset::<S>(),
+                expected::<S>(orientation),
+                "{name}, INVERT_TRANSPOSED_OFFSET = {}",
+                S::INVERT_TRANSPOSED_OFFSET
+            );
+        }
+    }
+
+    #[test]
+    fn offset_matches_orientation_table() {
+        check::<Spec<false>>();
+        check::<Spec<true>>();
+    }
+
+    #[test]
+    fn offset_of_mirrored_transposed_modes() {
+        let mv = Transform::new(240, 320, AddressMode::MV);
+        let mv_mx_my = Transform::new(
+            240,
+            320,
+            AddressMode::MV | AddressMode::MX | AddressMode::MY,
+        );
+        // (X, Y) offsets 2, 1 and rotated 4, 3
+        assert_eq!(mv.offset::<Spec<false>>(), (1, 4));
+        assert_eq!(mv_mx_my.offset::<Spec<false>>(), (3, 2));
+        assert_eq!(mv.offset::<Spec<true>>(), (1, 2));
+        assert_eq!(mv_mx_my.offset::<Spec<true>>(), (3, 4));
+    }
+}
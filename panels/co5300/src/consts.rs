@@ -26,13 +26,19 @@ pub const CONTINUE_WRITE_RAM: u8 = 0x3C;
 pub const WBRIGHT: u8 = 0x51; // Write brightness
 pub const RBRIGHT: u8 = 0x52; // Read brightness
 pub const WRITE_CTRL_DISPLAY: u8 = 0x53;
-pub const WRHBMDISBV: u8 = 0x63;
+pub const WRHBMDISBV: u8 = 0x63; // Write HBM brightness
 pub const DISPLAY_MODE: u8 = 0xC2;
 pub const SPI_MODE: u8 = 0xC4;
 pub const PASSWD1: u8 = 0xF4;
 pub const PASSWD2: u8 = 0xF5;
 pub const CMD_PAGE_SWITCH: u8 = 0xFE;
 
+// WRITE_CTRL_DISPLAY parameters
+/// Brightness control on (BCTRL).
+pub const CTRL_DISPLAY_NORMAL: u8 = 0x20;
+/// Brightness control on, high brightness mode on (HBM[1:0] = 11b).
+pub const CTRL_DISPLAY_HBM: u8 = 0xE0;
+
 // Chip IDs
 pub const CHIP_ID_VAL: u32 = 0x530001;
 pub const CHIP_ID_ALT: u32 = 0x331100;
@@ -0,0 +1,73 @@
+//! Host interface and command page types used during initialization.
+
+/// Command page selected with `CMD_PAGE_SWITCH` (Command 0xFE).
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandPage {
+    /// User command set (standard DCS commands).
+    User = 0x00,
+    /// Manufacturer command set.
+    Manufacturer = 0x20,
+}
+
+impl CommandPage {
+    /// Returns the command page for a `CMD_PAGE_SWITCH` parameter.
+    ///
+    /// Panics for parameters other than `0x00` and `0x20`.
+    pub const fn from_param(param: u8) -> Self {
+        match param {
+            0x00 => Self::User,
+            0x20 => Self::Manufacturer,
+            _ => panic!("unsupported command page"),
+        }
+    }
+}
+
+/// First password key (Command 0xF4), written on the selected [`CommandPage`].
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Passwd1 {
+    /// Unlocks the protected registers, together with [`Passwd2::Unlock`].
+    Unlock = 0x5A,
+    /// Locks the protected registers again.
+    Lock = 0xA5,
+}
+
+/// Second password key (Command 0xF5).
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Passwd2 {
+    /// Unlocks the protected registers, together with [`Passwd1::Unlock`].
+    Unlock = 0x59,
+    /// Locks the protected registers again.
+    Lock = 0xA5,
+}
+
+/// Host interface used to write the frame memory.
+///
+/// The interface itself is selected by the IM pins of the panel; this only configures
+/// SPI Mode Control (Command 0xC4) to match it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceMode {
+    /// 1-line SPI (3- or 4-wire).
+    Spi,
+    /// Quad SPI with flash-style framing, see [`QspiFlashBus`](display_driver::bus::QspiFlashBus).
+    Qspi,
+    /// Dual SPI (DSPI), pixel data on 2 lines.
+    DualSpi,
+}
+
+impl InterfaceMode {
+    /// B7 - Enable frame memory writes over the SPI interfaces (SPI_WRAM)
+    pub const SPI_WRAM: u8 = 0x80;
+    /// B5 - Dual SPI data lines (DSPI_EN)
+    pub const DSPI_EN: u8 = 0x20;
+
+    /// Returns the SPI Mode Control parameter for this interface.
+    pub const fn spi_mode_param(self) -> u8 {
+        match self {
+            Self::Spi | Self::Qspi => Self::SPI_WRAM,
+            Self::DualSpi => Self::SPI_WRAM | Self::DSPI_EN,
+        }
+    }
+}
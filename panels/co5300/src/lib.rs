@@ -5,18 +5,25 @@ use embedded_hal_async::delay::DelayNs;
 
 use display_driver::bus::DisplayBus;
 use display_driver::panel::initseq::{sequenced_init, InitStep};
-use display_driver::panel::reset::{LCDResetOption, LCDResetHandler};
-use display_driver::panel::{Orientation, Panel, PanelSetBrightness};
+use display_driver::panel::reset::{LCDResetHandler, LCDResetOption};
+use display_driver::panel::shape::VisibleShape;
+use display_driver::panel::{Mirror, Orientation, Panel, PanelSetBrightness};
 
-use display_driver::{ColorFormat, DisplayError};
+use display_driver::{Area, ColorFormat, DisplayError};
 
 // Use GenericMipidcs to handle standard DCS operations
-use display_driver_mipidcs::{consts::*, dcs_types::AddressMode, GenericMipidcs};
+use display_driver_mipidcs::{
+    consts::*,
+    dcs_types::{AddressMode, AddressRange, PixelFormat, PixelFormatType},
+    GenericMipidcs,
+};
 
 pub mod consts;
+pub mod interface;
 pub mod spec;
 
 use consts::*;
+pub use interface::{CommandPage, InterfaceMode, Passwd1, Passwd2};
 use spec::Co5300Spec;
 
 /// Driver for the CO5300 AMOLED display controller.
@@ -28,6 +35,32 @@ where
 {
     /// Inner generic driver for standard functionality.
     inner: GenericMipidcs<B, Spec, RST>,
+    /// Brightness last set with `set_brightness`, restored when leaving the always-on display mode.
+    brightness: u8,
+    /// Whether the high brightness mode is enabled.
+    hbm: bool,
+    /// Whether the always-on display mode is active.
+    aod: bool,
+}
+
+/// Always-on display (AOD) settings, see [`Co5300::enter_aod`].
+#[derive(Debug, Clone, Copy)]
+pub struct AodConfig {
+    /// The area that stays visible, in unrotated panel coordinates. `None` keeps the whole screen.
+    ///
+    /// It must follow the controller's 2-pixel alignment.
+    pub area: Option<Area>,
+    /// Brightness in AOD mode (0-255).
+    pub brightness: u8,
+}
+
+impl Default for AodConfig {
+    fn default() -> Self {
+        Self {
+            area: None,
+            brightness: 0x20,
+        }
+    }
 }
 
 impl<Spec, RST, B> Co5300<Spec, RST, B>
@@ -40,20 +73,151 @@ where
     pub fn new(reset_pin: LCDResetOption<RST>) -> Self {
         Self {
             inner: GenericMipidcs::new(reset_pin),
+            brightness: 0xFF,
+            hbm: false,
+            aod: false,
         }
     }
 
     /// Sets the display brightness (0-255).
+    ///
+    /// In AOD mode, the new brightness only takes effect after [`exit_aod`](Self::exit_aod).
     pub async fn set_brightness(
         &mut self,
         bus: &mut B,
         value: u8,
+    ) -> Result<(), DisplayError<B::Error>> {
+        self.brightness = value;
+        if self.aod {
+            return Ok(());
+        }
+        self.write_brightness(bus, value).await
+    }
+
+    async fn write_brightness(
+        &mut self,
+        bus: &mut B,
+        value: u8,
     ) -> Result<(), DisplayError<B::Error>> {
         bus.write_cmd_with_params(&[WBRIGHT], &[value])
             .await
             .map_err(DisplayError::BusError)
     }
 
+    /// Sets the brightness used in high brightness mode (0-255).
+    pub async fn set_hbm_brightness(
+        &mut self,
+        bus: &mut B,
+        value: u8,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[WRHBMDISBV], &[value])
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Enables or disables the high brightness mode (HBM), e.g. for outdoor use.
+    ///
+    /// While enabled, the display uses the brightness set with
+    /// [`set_hbm_brightness`](Self::set_hbm_brightness) (255 after init). HBM is suspended in AOD
+    /// mode.
+    pub async fn set_high_brightness_mode(
+        &mut self,
+        bus: &mut B,
+        enabled: bool,
+    ) -> Result<(), DisplayError<B::Error>> {
+        self.hbm = enabled;
+        if self.aod {
+            return Ok(());
+        }
+        self.write_ctrl_display(bus, enabled).await
+    }
+
+    /// Returns whether the high brightness mode is enabled.
+    pub fn is_high_brightness_mode(&self) -> bool {
+        self.hbm
+    }
+
+    async fn write_ctrl_display(
+        &mut self,
+        bus: &mut B,
+        hbm: bool,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let param = if hbm {
+            CTRL_DISPLAY_HBM
+        } else {
+            CTRL_DISPLAY_NORMAL
+        };
+        bus.write_cmd_with_params(&[WRITE_CTRL_DISPLAY], &[param])
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Enters the always-on display (AOD) mode.
+    ///
+    /// This suspends HBM, lowers the brightness, restricts the display to `config.area` (partial
+    /// mode) and enters idle mode (8 colors), which together reduce the power consumption to a
+    /// fraction. The frame memory outside the area is kept.
+    ///
+    /// Returns [`DisplayError::UnalignedArea`] if the area is not 2-pixel aligned.
+    pub async fn enter_aod(
+        &mut self,
+        bus: &mut B,
+        config: AodConfig,
+    ) -> Result<(), DisplayError<B::Error>> {
+        if let Some(area) = config.area {
+            if area.w == 0 || area.h == 0 {
+                return Err(DisplayError::InvalidArgs);
+            }
+            if !area.x.is_multiple_of(2)
+                || !area.y.is_multiple_of(2)
+                || !area.w.is_multiple_of(2)
+                || !area.h.is_multiple_of(2)
+            {
+                return Err(DisplayError::UnalignedArea);
+            }
+        }
+
+        if self.hbm {
+            self.write_ctrl_display(bus, false).await?;
+        }
+        self.write_brightness(bus, config.brightness).await?;
+
+        if let Some(area) = config.area {
+            let (x1, y1) = area.bottom_right();
+            let (x_offset, y_offset) = (Spec::PHYSICAL_X_OFFSET, Spec::PHYSICAL_Y_OFFSET);
+            // The CO5300 swaps the DCS partial commands: columns are 0x30, rows are 0x31
+            let columns = AddressRange::new(area.x + x_offset, x1 + x_offset);
+            bus.write_cmd_with_params(&[PART_CASET], columns.as_bytes())
+                .await?;
+            let rows = AddressRange::new(area.y + y_offset, y1 + y_offset);
+            bus.write_cmd_with_params(&[PART_RASET], rows.as_bytes())
+                .await?;
+            self.inner.enter_partial_mode(bus).await?;
+        }
+        self.inner.set_idle_mode(bus, true).await?;
+
+        self.aod = true;
+        Ok(())
+    }
+
+    /// Leaves the always-on display mode, restoring normal mode, the brightness and HBM.
+    pub async fn exit_aod(&mut self, bus: &mut B) -> Result<(), DisplayError<B::Error>> {
+        self.inner.set_idle_mode(bus, false).await?;
+        self.inner.enter_normal_mode(bus).await?;
+        self.write_brightness(bus, self.brightness).await?;
+        if self.hbm {
+            self.write_ctrl_display(bus, true).await?;
+        }
+
+        self.aod = false;
+        Ok(())
+    }
+
+    /// Returns whether the always-on display mode is active.
+    pub fn is_aod(&self) -> bool {
+        self.aod
+    }
+
     delegate::delegate! {
         to self.inner {
             pub async fn set_invert_mode(
@@ -73,28 +237,40 @@ where
         }
     }
 
+    /// Pixel format parameter for [`Co5300Spec::COLOR_FORMAT`].
+    const PIXEL_FORMAT: PixelFormat = {
+        match Spec::COLOR_FORMAT {
+            ColorFormat::RGB565 | ColorFormat::RGB666 | ColorFormat::RGB888 => {}
+            _ => panic!("CO5300 only supports RGB565, RGB666 and RGB888"),
+        }
+        match PixelFormatType::from_bit_count(Spec::COLOR_FORMAT.size_bits()) {
+            Some(pf_type) => PixelFormat::dbi_and_dpi(pf_type),
+            None => unreachable!(),
+        }
+    };
+
     /// Initialization sequence for CO5300.
     const INIT_STEPS: &'static [InitStep<'static>] = &[
         // Unlock Sequence
-        InitStep::CommandWithParams(CMD_PAGE_SWITCH, &[Spec::INIT_PAGE_PARAM]),
-        InitStep::CommandWithParams(PASSWD1, &[0x5A]),
-        InitStep::CommandWithParams(PASSWD2, &[0x59]),
+        InitStep::CommandWithParams(CMD_PAGE_SWITCH, &[Spec::INIT_PAGE as u8]),
+        InitStep::CommandWithParams(PASSWD1, &[Passwd1::Unlock as u8]),
+        InitStep::CommandWithParams(PASSWD2, &[Passwd2::Unlock as u8]),
         // Lock Sequence
-        InitStep::CommandWithParams(CMD_PAGE_SWITCH, &[0x20]),
-        InitStep::CommandWithParams(PASSWD1, &[0xA5]),
-        InitStep::CommandWithParams(PASSWD2, &[0xA5]),
+        InitStep::CommandWithParams(CMD_PAGE_SWITCH, &[CommandPage::Manufacturer as u8]),
+        InitStep::CommandWithParams(PASSWD1, &[Passwd1::Lock as u8]),
+        InitStep::CommandWithParams(PASSWD2, &[Passwd2::Lock as u8]),
         // Configuration
-        InitStep::CommandWithParams(CMD_PAGE_SWITCH, &[0x00]),
-        InitStep::CommandWithParams(SPI_MODE, &[0x80]),
-        InitStep::CommandWithParams(COLOR_MODE, &[0x55]), // Default to RGB565
+        InitStep::CommandWithParams(CMD_PAGE_SWITCH, &[CommandPage::User as u8]),
+        InitStep::CommandWithParams(SPI_MODE, &[Spec::INTERFACE_MODE.spi_mode_param()]),
+        InitStep::CommandWithParams(COLOR_MODE, &[Self::PIXEL_FORMAT.0]),
+        InitStep::CommandWithParams(SET_ADDRESS_MODE, &[AddressMode::initial::<Spec>().bits()]),
         InitStep::CommandWithParams(TEARING_EFFECT_ON, &[0x00]),
-        InitStep::CommandWithParams(WRITE_CTRL_DISPLAY, &[0x20]),
+        InitStep::CommandWithParams(WRITE_CTRL_DISPLAY, &[CTRL_DISPLAY_NORMAL]),
         InitStep::CommandWithParams(WRHBMDISBV, &[0xFF]),
         // Power On
         InitStep::SingleCommand(SLEEP_OUT),
         InitStep::DelayMs(120),
-        InitStep::SingleCommand(DISPLAY_ON),
-        InitStep::DelayMs(70),
+        // Display On is sent later by the driver, after the frame memory has been prepared.
     ];
 }
 
@@ -106,6 +282,8 @@ where
 {
     const CMD_LEN: usize = 1;
     const PIXEL_WRITE_CMD: [u8; 4] = [WRITE_RAM, 0, 0, 0];
+    const DISPLAY_ON_DELAY_MS: u8 = 70;
+    const POWER_OFF_DELAY_MS: u8 = Spec::POWER_OFF_DELAY_MS;
 
     fn x_alignment(&self) -> u16 {
         2
@@ -115,7 +293,11 @@ where
         2
     }
 
-    async fn init<D: DelayNs>(&mut self, bus: &mut B, mut delay: D) -> Result<(), B::Error> {
+    async fn init<D: DelayNs>(
+        &mut self,
+        bus: &mut B,
+        mut delay: D,
+    ) -> Result<(), DisplayError<B::Error>> {
         let mut reseter = LCDResetHandler::new(
             &mut self.inner.reset_pin,
             bus,
@@ -126,12 +308,43 @@ where
         );
         reseter.reset().await?;
 
+        // The reset leaves AOD, HBM and the Address Mode
+        self.aod = false;
+        self.hbm = false;
+        self.inner.reset_address_mode();
+
         // Execute Initialization Sequence
         // copied() only copies the items during iteration; it does not copy the entire sequence
-        sequenced_init(Self::INIT_STEPS.iter().copied(), &mut delay, bus).await
+        sequenced_init(Self::INIT_STEPS.iter().copied(), &mut delay, bus)
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Only `RGB565`, `RGB666` and `RGB888` are supported, other formats return
+    /// [`DisplayError::Unsupported`].
+    async fn set_color_format(
+        &mut self,
+        bus: &mut B,
+        color_format: ColorFormat,
+    ) -> Result<(), DisplayError<B::Error>> {
+        match color_format {
+            ColorFormat::RGB565 | ColorFormat::RGB666 | ColorFormat::RGB888 => {
+                self.inner.set_color_format(bus, color_format).await
+            }
+            _ => Err(DisplayError::Unsupported),
+        }
     }
 
     delegate::delegate! {
+        to &mut self.inner {
+            #[through(Panel::<B>)]
+            async fn set_display_on(
+                &mut self,
+                bus: &mut B,
+                on: bool,
+            ) -> Result<(), DisplayError<B::Error>>;
+        }
+
         to self.inner {
             fn width(&self) -> u16;
 
@@ -139,6 +352,8 @@ where
 
             fn size(&self) -> (u16, u16);
 
+            fn visible_shape(&self) -> VisibleShape;
+
             async fn set_window(
                 &mut self,
                 bus: &mut B,
@@ -148,16 +363,23 @@ where
                 y1: u16,
             ) -> Result<(), DisplayError<B::Error>>;
 
-            async fn set_color_format(
+            async fn set_orientation(
                 &mut self,
                 bus: &mut B,
-                color_format: ColorFormat,
+                orientation: Orientation,
             ) -> Result<(), DisplayError<B::Error>>;
 
-            async fn set_orientation(
+            async fn set_mirror(
                 &mut self,
                 bus: &mut B,
-                orientation: Orientation,
+                mirror: Mirror,
+            ) -> Result<(), DisplayError<B::Error>>;
+
+            async fn set_sleep<D: DelayNs>(
+                &mut self,
+                bus: &mut B,
+                sleep: bool,
+                delay: D,
             ) -> Result<(), DisplayError<B::Error>>;
         }
     }
@@ -174,8 +396,109 @@ where
         bus: &mut B,
         brightness: u8,
     ) -> Result<(), DisplayError<B::Error>> {
-        bus.write_cmd_with_params(&[WBRIGHT], &[brightness])
-            .await
-            .map_err(DisplayError::BusError)
+        Co5300::set_brightness(self, bus, brightness).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::{
+        convert::Infallible,
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+    use std::vec::Vec;
+
+    use display_driver::bus::{ErrorType, Metadata, QspiFlashBus};
+    use display_driver::{DisplayDriver, NoPin, SolidColor};
+
+    use super::*;
+    use crate::spec::AM196Q410502LK_196;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// A bus recording the commands and pixel writes reaching it, each with its parameters.
+    #[derive(Default)]
+    struct RecordingBus {
+        cmds: Vec<Vec<u8>>,
+        pixel_writes: Vec<Vec<u8>>,
+    }
+
+    impl ErrorType for RecordingBus {
+        type Error = Infallible;
+    }
+
+    impl DisplayBus for RecordingBus {
+        async fn write_cmd(&mut self, cmd: &[u8]) -> Result<(), Self::Error> {
+            self.cmds.push(cmd.to_vec());
+            Ok(())
+        }
+
+        async fn write_cmd_with_params(
+            &mut self,
+            cmd: &[u8],
+            params: &[u8],
+        ) -> Result<(), Self::Error> {
+            self.cmds.push([cmd, params].concat());
+            Ok(())
+        }
+
+        async fn write_pixels(
+            &mut self,
+            cmd: &[u8],
+            data: &[u8],
+            _metadata: Metadata,
+        ) -> Result<(), DisplayError<Self::Error>> {
+            self.pixel_writes.push([cmd, data].concat());
+            Ok(())
+        }
+    }
+
+    fn qspi_color_format(format: ColorFormat, param: u8, rgb: [u8; 3], pixel: [u8; 3]) {
+        let panel = Co5300::<AM196Q410502LK_196, NoPin, _>::new(LCDResetOption::Software);
+        let mut driver = DisplayDriver::new(QspiFlashBus::new(RecordingBus::default()), panel);
+        block_on(driver.set_color_format(format)).unwrap();
+
+        let color = SolidColor::from_rgb888(format, rgb).unwrap();
+        block_on(driver.fill_solid_chunked::<64>(color, Area::from_origin(2, 2))).unwrap();
+
+        let bus = driver.bus.into_inner();
+        assert_eq!(bus.cmds[0], [0x02, 0x00, COLOR_MODE, 0x00, param]);
+        let expected: Vec<u8> = [0x32, 0x00, WRITE_RAM, 0x00]
+            .into_iter()
+            .chain(pixel.repeat(4))
+            .collect();
+        assert_eq!(bus.pixel_writes, [expected]);
+    }
+
+    #[test]
+    fn qspi_rgb888() {
+        qspi_color_format(
+            ColorFormat::RGB888,
+            0x77,
+            [0x12, 0x34, 0x56],
+            [0x12, 0x34, 0x56],
+        );
+    }
+
+    #[test]
+    fn qspi_rgb666() {
+        qspi_color_format(
+            ColorFormat::RGB666,
+            0x66,
+            [0x13, 0x37, 0xFF],
+            [0x10, 0x34, 0xFC],
+        );
     }
 }
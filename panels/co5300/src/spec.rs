@@ -1,14 +1,34 @@
 #![allow(non_camel_case_types)]
 
+use display_driver::ColorFormat;
 pub use display_driver_mipidcs::PanelSpec;
 
+pub use crate::interface::{CommandPage, InterfaceMode};
+
 /// Display Specification Trait.
 ///
 /// Defines resolution, offsets, and specific initialization behaviors
 pub trait Co5300Spec: PanelSpec {
-    /// Parameter for `REG_CMD_PAGE_SWITCH` during initialization.
+    /// Command page on which the protected registers are unlocked during initialization.
+    /// [`CommandPage::User`] for specific panels, [`CommandPage::Manufacturer`] for else.
+    ///
+    /// Either this or the deprecated `INIT_PAGE_PARAM` must be set.
+    #[allow(deprecated)]
+    const INIT_PAGE: CommandPage = CommandPage::from_param(Self::INIT_PAGE_PARAM);
+
+    /// Parameter for `CMD_PAGE_SWITCH` during initialization.
     /// `0x00` for specific panels, `0x20` for else.
-    const INIT_PAGE_PARAM: u8;
+    #[deprecated(note = "use `INIT_PAGE` instead")]
+    const INIT_PAGE_PARAM: u8 = Self::INIT_PAGE as u8;
+
+    /// Host interface, configures SPI Mode Control (Command 0xC4).
+    const INTERFACE_MODE: InterfaceMode = InterfaceMode::Qspi;
+
+    /// Color format set during initialization (Command 0x3A).
+    ///
+    /// Must be one of `RGB565`, `RGB666` or `RGB888`. It can be changed later with
+    /// `set_color_format`, e.g. by `DisplayDriverBuilder::with_color_format`.
+    const COLOR_FORMAT: ColorFormat = ColorFormat::RGB565;
 
     /// Whether to force `read_id` to succeed regardless of hardware response.
     /// Corresponds to the logic in `LCD_ReadID`.
@@ -24,7 +44,7 @@ impl PanelSpec for AM196Q410502LK_196 {
     const PHYSICAL_Y_OFFSET: u16 = 0;
 }
 impl Co5300Spec for AM196Q410502LK_196 {
-    const INIT_PAGE_PARAM: u8 = 0x00;
+    const INIT_PAGE: CommandPage = CommandPage::User;
     const IGNORE_ID_CHECK: bool = true;
 }
 
@@ -37,7 +57,7 @@ impl PanelSpec for AM178Q368448LK_178 {
     const PHYSICAL_Y_OFFSET: u16 = 0;
 }
 impl Co5300Spec for AM178Q368448LK_178 {
-    const INIT_PAGE_PARAM: u8 = 0x00;
+    const INIT_PAGE: CommandPage = CommandPage::User;
     const IGNORE_ID_CHECK: bool = true;
 }
 
@@ -50,7 +70,7 @@ impl PanelSpec for AM151Q466466LK_151_C {
     const PHYSICAL_Y_OFFSET: u16 = 0;
 }
 impl Co5300Spec for AM151Q466466LK_151_C {
-    const INIT_PAGE_PARAM: u8 = 0x00;
+    const INIT_PAGE: CommandPage = CommandPage::User;
     const IGNORE_ID_CHECK: bool = true;
 }
 
@@ -63,7 +83,7 @@ impl PanelSpec for AM200Q460460LK_200 {
     const PHYSICAL_Y_OFFSET: u16 = 0;
 }
 impl Co5300Spec for AM200Q460460LK_200 {
-    const INIT_PAGE_PARAM: u8 = 0x00;
+    const INIT_PAGE: CommandPage = CommandPage::User;
     const IGNORE_ID_CHECK: bool = true;
 }
 
@@ -76,7 +96,7 @@ impl PanelSpec for H0198S005AMT005_V0_195 {
     const PHYSICAL_Y_OFFSET: u16 = 0;
 }
 impl Co5300Spec for H0198S005AMT005_V0_195 {
-    const INIT_PAGE_PARAM: u8 = 0x00;
+    const INIT_PAGE: CommandPage = CommandPage::User;
     const IGNORE_ID_CHECK: bool = true;
 }
 
@@ -89,7 +109,7 @@ impl PanelSpec for Amoled_185Inch_390x450 {
     const PHYSICAL_Y_OFFSET: u16 = 0;
 }
 impl Co5300Spec for Amoled_185Inch_390x450 {
-    const INIT_PAGE_PARAM: u8 = 0x20;
+    const INIT_PAGE: CommandPage = CommandPage::Manufacturer;
     const IGNORE_ID_CHECK: bool = true;
 }
 
@@ -101,6 +121,6 @@ impl PanelSpec for GenericCo5300 {
     const PHYSICAL_Y_OFFSET: u16 = 0;
 }
 impl Co5300Spec for GenericCo5300 {
-    const INIT_PAGE_PARAM: u8 = 0x20;
+    const INIT_PAGE: CommandPage = CommandPage::Manufacturer;
     const IGNORE_ID_CHECK: bool = true;
 }
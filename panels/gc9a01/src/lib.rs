@@ -6,7 +6,8 @@ use embedded_hal_async::delay::DelayNs;
 use display_driver::bus::DisplayBus;
 use display_driver::panel::initseq::{sequenced_init, InitStep};
 use display_driver::panel::reset::{LCDResetHandler, LCDResetOption};
-use display_driver::panel::{Orientation, Panel, PanelSetBrightness};
+use display_driver::panel::shape::VisibleShape;
+use display_driver::panel::{Mirror, Orientation, Panel, PanelSetBrightness};
 
 use display_driver::{ColorFormat, DisplayError};
 
@@ -91,11 +92,7 @@ where
         ),
         InitStep::CommandWithParams(
             mipidcs::SET_ADDRESS_MODE,
-            &[if Spec::BGR {
-                AddressMode::BGR.bits()
-            } else {
-                0u8
-            }],
+            &[AddressMode::initial::<Spec>().bits()],
         ),
         // Pixel Format Set
         InitStep::CommandWithParams(mipidcs::SET_PIXEL_FORMAT, &[0x05]), // 16bit MCU
@@ -104,25 +101,24 @@ where
         InitStep::CommandWithParams(0xBC, &[0x00]),
         InitStep::CommandWithParams(0xFF, &[0x60, 0x01, 0x04]),
         // Power Control
-        InitStep::CommandWithParams(POWER_CONTROL_2, &[0x13]), // Vreg1a voltage
-        InitStep::CommandWithParams(POWER_CONTROL_3, &[0x13]), // Vreg1b voltage
-        InitStep::CommandWithParams(POWER_CONTROL_4, &[0x22]), // Vreg2a voltage
+        InitStep::CommandWithParams(POWER_CONTROL_2, &[Spec::POWER_CONTROL_2_PARAM]), // Vreg1a voltage
+        InitStep::CommandWithParams(POWER_CONTROL_3, &[Spec::POWER_CONTROL_3_PARAM]), // Vreg1b voltage
+        InitStep::CommandWithParams(POWER_CONTROL_4, &[Spec::POWER_CONTROL_4_PARAM]), // Vreg2a voltage
         InitStep::CommandWithParams(0xBE, &[0x11]),
         InitStep::CommandWithParams(0xE1, &[0x10, 0x0E]),
         InitStep::CommandWithParams(0xDF, &[0x21, 0x0C, 0x02]),
         // Gamma
-        InitStep::CommandWithParams(SET_GAMMA_1, &[0x45, 0x09, 0x08, 0x08, 0x26, 0x2A]),
-        InitStep::CommandWithParams(SET_GAMMA_2, &[0x43, 0x70, 0x72, 0x36, 0x37, 0x6F]),
-        InitStep::CommandWithParams(SET_GAMMA_3, &[0x45, 0x09, 0x08, 0x08, 0x26, 0x2A]),
-        InitStep::CommandWithParams(SET_GAMMA_4, &[0x43, 0x70, 0x72, 0x36, 0x37, 0x6F]),
+        InitStep::CommandWithParams(SET_GAMMA_1, &Spec::SET_GAMMA_1_PARAMS),
+        InitStep::CommandWithParams(SET_GAMMA_2, &Spec::SET_GAMMA_2_PARAMS),
+        InitStep::CommandWithParams(SET_GAMMA_3, &Spec::SET_GAMMA_3_PARAMS),
+        InitStep::CommandWithParams(SET_GAMMA_4, &Spec::SET_GAMMA_4_PARAMS),
         InitStep::CommandWithParams(0xED, &[0x1B, 0x0B]),
         InitStep::CommandWithParams(0xAE, &[0x77]),
         InitStep::CommandWithParams(0xCD, &[0x63]),
-        // TODO: Adafruit says remove this line may solve some problems
-        // InitStep::CommandWithParams(0x70, &[0x07, 0x07, 0x04, 0x0E, 0x0F, 0x09, 0x07, 0x08, 0x03]),
-
+        // Adafruit says removing this line may solve some problems, so it is optional
+        InitStep::maybe_cmd_with(0x70, Spec::REG_70_PARAMS),
         // Frame Rate
-        InitStep::CommandWithParams(FRAME_RATE_CONTROL, &[0x34]),
+        InitStep::CommandWithParams(FRAME_RATE_CONTROL, &[Spec::FRAME_RATE_CONTROL_PARAM]),
         InitStep::CommandWithParams(
             0x62,
             &[
@@ -147,12 +143,10 @@ where
         InitStep::CommandWithParams(0x74, &[0x10, 0x85, 0x80, 0x00, 0x00, 0x4E, 0x00]),
         InitStep::CommandWithParams(0x98, &[0x3E, 0x07]),
         InitStep::SingleCommand(mipidcs::SET_TEAR_OFF),
-        InitStep::SingleCommand(mipidcs::ENTER_INVERT_MODE), // Display Inversion ON
-        InitStep::SingleCommand(mipidcs::EXIT_SLEEP_MODE),   // Sleep Out
+        InitStep::SingleCommand(mipidcs::EXIT_SLEEP_MODE), // Sleep Out
         // InitStep::SingleCommand(mipidcs::EXIT_IDLE_MODE), // Idle Mode OFF
         InitStep::DelayMs(120),
-        InitStep::SingleCommand(mipidcs::SET_DISPLAY_ON),
-        InitStep::DelayMs(20),
+        // Display On is sent later by the driver, after the frame memory has been prepared.
     ];
 }
 
@@ -164,8 +158,14 @@ where
 {
     const CMD_LEN: usize = 1;
     const PIXEL_WRITE_CMD: [u8; 4] = [mipidcs::WRITE_MEMORY_START, 0, 0, 0];
+    const DISPLAY_ON_DELAY_MS: u8 = 20;
+    const POWER_OFF_DELAY_MS: u8 = Spec::POWER_OFF_DELAY_MS;
 
-    async fn init<D: DelayNs>(&mut self, bus: &mut B, mut delay: D) -> Result<(), B::Error> {
+    async fn init<D: DelayNs>(
+        &mut self,
+        bus: &mut B,
+        mut delay: D,
+    ) -> Result<(), DisplayError<B::Error>> {
         // Hardware Reset
         let mut reseter = LCDResetHandler::new(
             &mut self.inner.reset_pin,
@@ -176,14 +176,26 @@ where
             Some(&[mipidcs::SOFT_RESET]),
         );
         reseter.reset().await?;
+        self.inner.reset_address_mode();
 
 
         // Execute Initialization Sequence
         // `copied()` only copies the items during iteration; it does not copy the entire sequence
-        sequenced_init(Self::INIT_STEPS.iter().copied(), &mut delay, bus).await
+        sequenced_init(Self::INIT_STEPS.iter().copied(), &mut delay, bus)
+            .await
+            .map_err(DisplayError::BusError)
     }
 
     delegate::delegate! {
+        to &mut self.inner {
+            #[through(Panel::<B>)]
+            async fn set_display_on(
+                &mut self,
+                bus: &mut B,
+                on: bool,
+            ) -> Result<(), DisplayError<B::Error>>;
+        }
+
         to self.inner {
             fn width(&self) -> u16;
 
@@ -191,6 +203,8 @@ where
 
             fn size(&self) -> (u16, u16);
 
+            fn visible_shape(&self) -> VisibleShape;
+
             async fn set_window(
                 &mut self,
                 bus: &mut B,
@@ -211,6 +225,19 @@ where
                 bus: &mut B,
                 orientation: Orientation,
             ) -> Result<(), DisplayError<B::Error>>;
+
+            async fn set_mirror(
+                &mut self,
+                bus: &mut B,
+                mirror: Mirror,
+            ) -> Result<(), DisplayError<B::Error>>;
+
+            async fn set_sleep<D: DelayNs>(
+                &mut self,
+                bus: &mut B,
+                sleep: bool,
+                delay: D,
+            ) -> Result<(), DisplayError<B::Error>>;
         }
     }
 }
@@ -1,7 +1,41 @@
+use display_driver::panel::shape::VisibleShape;
 use display_driver_mipidcs::PanelSpec;
 
 /// Specification for GC9A01 initialization differences.
-pub trait Gc9a01Spec: PanelSpec {}
+///
+/// All tuning parameters default to the values of the common generic modules, so most panels only
+/// need to configure their [`PanelSpec`].
+pub trait Gc9a01Spec: PanelSpec {
+    /// Power Control 2 (0xC3), Vreg1a voltage - 1 byte
+    const POWER_CONTROL_2_PARAM: u8 = 0x13;
+
+    /// Power Control 3 (0xC4), Vreg1b voltage - 1 byte
+    const POWER_CONTROL_3_PARAM: u8 = 0x13;
+
+    /// Power Control 4 (0xC9), Vreg2a voltage - 1 byte
+    const POWER_CONTROL_4_PARAM: u8 = 0x22;
+
+    /// Set Gamma 1 (0xF0) - 6 bytes
+    const SET_GAMMA_1_PARAMS: [u8; 6] = [0x45, 0x09, 0x08, 0x08, 0x26, 0x2A];
+
+    /// Set Gamma 2 (0xF1) - 6 bytes
+    const SET_GAMMA_2_PARAMS: [u8; 6] = [0x43, 0x70, 0x72, 0x36, 0x37, 0x6F];
+
+    /// Set Gamma 3 (0xF2) - 6 bytes
+    const SET_GAMMA_3_PARAMS: [u8; 6] = [0x45, 0x09, 0x08, 0x08, 0x26, 0x2A];
+
+    /// Set Gamma 4 (0xF3) - 6 bytes
+    const SET_GAMMA_4_PARAMS: [u8; 6] = [0x43, 0x70, 0x72, 0x36, 0x37, 0x6F];
+
+    /// Frame Rate Control (0xE8) - 1 byte
+    const FRAME_RATE_CONTROL_PARAM: u8 = 0x34;
+
+    /// Undocumented register 0x70 - 9 bytes
+    ///
+    /// Some vendor init sequences send `[0x07, 0x07, 0x04, 0x0E, 0x0F, 0x09, 0x07, 0x08, 0x03]`,
+    /// but it is known to cause problems on some modules, so it is not sent by default.
+    const REG_70_PARAMS: Option<&'static [u8; 9]> = None;
+}
 
 /// Generic 240x240 GC9A01 Panel
 pub struct Generic240x240Type1;
@@ -10,7 +44,8 @@ impl PanelSpec for Generic240x240Type1 {
     const PHYSICAL_WIDTH: u16 = 240;
     const PHYSICAL_HEIGHT: u16 = 240;
     const BGR: bool = true;
-    const INVERTED: bool = false;
+    const INVERTED: bool = true;
+    const VISIBLE_SHAPE: VisibleShape = VisibleShape::Circle;
 }
 
 impl Gc9a01Spec for Generic240x240Type1 {}
@@ -25,7 +60,8 @@ impl PanelSpec for Generic128x128Type1 {
     const PHYSICAL_X_OFFSET: u16 = 2;
     const PHYSICAL_Y_OFFSET: u16 = 1;
     const BGR: bool = true;
-    const INVERTED: bool = false;
+    const INVERTED: bool = true;
+    const VISIBLE_SHAPE: VisibleShape = VisibleShape::Circle;
 }
 
 impl Gc9a01Spec for Generic128x128Type1 {}
@@ -6,7 +6,8 @@ use embedded_hal_async::delay::DelayNs;
 use display_driver::bus::DisplayBus;
 use display_driver::panel::initseq::{sequenced_init, InitStep};
 use display_driver::panel::reset::{LCDResetOption, LCDResetHandler};
-use display_driver::panel::{Orientation, Panel};
+use display_driver::panel::shape::VisibleShape;
+use display_driver::panel::{Mirror, Orientation, Panel};
 
 use display_driver::{ColorFormat, DisplayError};
 
@@ -87,18 +88,12 @@ where
         ),
         InitStep::CommandWithParams(
             mipidcs::SET_ADDRESS_MODE,
-            &[if Spec::BGR {
-                AddressMode::BGR.bits()
-            } else {
-                0u8
-            }],
+            &[AddressMode::initial::<Spec>().bits()],
         ),
         // Gamma Correction
         InitStep::maybe_cmd_with(GMCTRP1, Spec::GMCTRP1_PARAMS),
         InitStep::maybe_cmd_with(GMCTRN1, Spec::GMCTRN1_PARAMS),
-        // Display On
-        InitStep::SingleCommand(mipidcs::SET_DISPLAY_ON),
-        InitStep::DelayMs(20), // Small delay after turning on
+        // Display On is sent later by the driver, after the frame memory has been prepared.
     ];
 }
 
@@ -110,8 +105,14 @@ where
 {
     const CMD_LEN: usize = 1;
     const PIXEL_WRITE_CMD: [u8; 4] = [mipidcs::WRITE_MEMORY_START, 0, 0, 0];
-
-    async fn init<D: DelayNs>(&mut self, bus: &mut B, mut delay: D) -> Result<(), B::Error> {
+    const DISPLAY_ON_DELAY_MS: u8 = 20;
+    const POWER_OFF_DELAY_MS: u8 = Spec::POWER_OFF_DELAY_MS;
+
+    async fn init<D: DelayNs>(
+        &mut self,
+        bus: &mut B,
+        mut delay: D,
+    ) -> Result<(), DisplayError<B::Error>> {
         // Hardware Reset
         let mut reseter = LCDResetHandler::new(
             &mut self.inner.reset_pin,
@@ -122,14 +123,26 @@ where
             Some(&[mipidcs::SOFT_RESET]),
         );
         reseter.reset().await?;
+        self.inner.reset_address_mode();
 
 
         // Execute Initialization Sequence
         // copied() only copies the items during iteration; it does not copy the entire sequence
-        sequenced_init(Self::INIT_STEPS.iter().copied(), &mut delay, bus).await
+        sequenced_init(Self::INIT_STEPS.iter().copied(), &mut delay, bus)
+            .await
+            .map_err(DisplayError::BusError)
     }
 
     delegate::delegate! {
+        to &mut self.inner {
+            #[through(Panel::<B>)]
+            async fn set_display_on(
+                &mut self,
+                bus: &mut B,
+                on: bool,
+            ) -> Result<(), DisplayError<B::Error>>;
+        }
+
         to self.inner {
             fn width(&self) -> u16;
 
@@ -137,6 +150,8 @@ where
 
             fn size(&self) -> (u16, u16);
 
+            fn visible_shape(&self) -> VisibleShape;
+
             async fn set_window(
                 &mut self,
                 bus: &mut B,
@@ -157,6 +172,19 @@ where
                 bus: &mut B,
                 orientation: Orientation,
             ) -> Result<(), DisplayError<B::Error>>;
+
+            async fn set_mirror(
+                &mut self,
+                bus: &mut B,
+                mirror: Mirror,
+            ) -> Result<(), DisplayError<B::Error>>;
+
+            async fn set_sleep<D: DelayNs>(
+                &mut self,
+                bus: &mut B,
+                sleep: bool,
+                delay: D,
+            ) -> Result<(), DisplayError<B::Error>>;
         }
     }
 }
@@ -0,0 +1,127 @@
+//! Content Adaptive Brightness Control (CABC) types.
+//!
+//! With CABC, the ST7789 analyses the image content and lowers the backlight duty on its `LEDPWM`
+//! output while boosting the pixel data, so the perceived image stays the same at lower backlight
+//! power. This only has an effect if the backlight driver is fed from the `LEDPWM` pin.
+
+use bitflags::bitflags;
+
+/// CABC mode, the `C[1:0]` bits of Write Content Adaptive Brightness Control (Command 0x55).
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CabcMode {
+    /// CABC disabled.
+    Off = 0b00,
+    /// User interface mode, the least aggressive.
+    UserInterface = 0b01,
+    /// Still picture mode.
+    StillPicture = 0b10,
+    /// Moving image mode, the most aggressive.
+    MovingImage = 0b11,
+}
+
+impl CabcMode {
+    /// Extracts the CABC mode from a WRCACE / RDCABC value.
+    pub const fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0b00 => Self::Off,
+            0b01 => Self::UserInterface,
+            0b10 => Self::StillPicture,
+            _ => Self::MovingImage,
+        }
+    }
+}
+
+/// Color enhancement level, the `CECTRL` and `CE[1:0]` bits of Command 0x55.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorEnhancement {
+    /// Color enhancement disabled.
+    Off = 0x00,
+    /// Low enhancement.
+    Low = 0x80,
+    /// Medium enhancement.
+    Medium = 0x90,
+    /// High enhancement.
+    High = 0xB0,
+}
+
+/// Parameter of Write Content Adaptive Brightness Control and Color Enhancement (Command 0x55).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cace(pub u8);
+
+impl Cace {
+    pub const fn new(mode: CabcMode, color_enhancement: ColorEnhancement) -> Self {
+        Self(mode as u8 | color_enhancement as u8)
+    }
+
+    pub const fn as_bytes(self) -> [u8; 1] {
+        [self.0]
+    }
+}
+
+bitflags! {
+    /// Write CTRL Display flags (Command 0x53).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct DisplayControl: u8 {
+        /// B2 - Backlight Control (BL)
+        ///
+        /// 0 = `LEDPWM` is held off, 1 = `LEDPWM` is driven.
+        const BACKLIGHT = 0b0000_0100;
+
+        /// B3 - Display Dimming (DD)
+        ///
+        /// Fades smoothly between brightness levels (both for WRDISBV and CABC changes),
+        /// instead of switching immediately. Only effective while `BRIGHTNESS_CONTROL` is set.
+        const DIMMING = 0b0000_1000;
+
+        /// B5 - Brightness Control Block (BCTRL)
+        ///
+        /// Enables the brightness registers (WRDISBV, CABC). When cleared, `LEDPWM` runs at full
+        /// duty.
+        const BRIGHTNESS_CONTROL = 0b0010_0000;
+    }
+}
+
+impl DisplayControl {
+    /// Brightness control with the backlight on, as needed for WRDISBV and CABC to take effect.
+    pub const fn brightness_control() -> Self {
+        Self::from_bits_retain(Self::BRIGHTNESS_CONTROL.bits() | Self::BACKLIGHT.bits())
+    }
+
+    pub const fn as_bytes(&self) -> [u8; 1] {
+        [self.bits()]
+    }
+}
+
+bitflags! {
+    /// CABC Control flags (Command 0xC7).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct CabcControl: u8 {
+        /// B0 - `LEDPWM` Polarity (PWMPOL)
+        ///
+        /// 0 = Active high, 1 = Active low.
+        const PWM_POLARITY_LOW = 0b0000_0001;
+
+        /// B1 - `LEDPWM` Fix (PWMFIX)
+        ///
+        /// Holds `LEDPWM` at its current level.
+        const PWM_FIX = 0b0000_0010;
+
+        /// B2 - `LEDPWM` in Display Off (DPOFPWM)
+        ///
+        /// Initial state of `LEDPWM` while the display is off. 0 = Low, 1 = High.
+        const DISPLAY_OFF_PWM_HIGH = 0b0000_0100;
+
+        /// B3 - `LEDON` Reverse (LEDONREV)
+        ///
+        /// Reverses the polarity of the `LEDON` output.
+        const LEDON_REVERSE = 0b0000_1000;
+    }
+}
+
+impl CabcControl {
+    pub const fn as_bytes(&self) -> [u8; 1] {
+        [self.bits()]
+    }
+}
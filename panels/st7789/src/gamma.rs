@@ -0,0 +1,167 @@
+//! Digital gamma lookup tables (Commands 0xBA, 0xE2, 0xE3).
+//!
+//! Digital gamma remaps the red and blue channels through a 64-entry table before the analog
+//! gamma (PVGAMCTRL / NVGAMCTRL) is applied. Green is the reference channel and has no table.
+//! This is mainly used to colour-match panels, e.g. to correct a white point shift.
+//!
+//! The generators are `const fn`, so tables can be used directly in a [`St7789Spec`](crate::St7789Spec):
+//! ```
+//! use display_driver_st7789::DigitalGammaLut;
+//!
+//! const RED_LUT: DigitalGammaLut = DigitalGammaLut::gamma(1.1);
+//! const BLUE_LUT: DigitalGammaLut = DigitalGammaLut::linear().scaled(95, 100);
+//!
+//! const DGMLUTR_PARAMS: Option<&'static [u8; 64]> = Some(&RED_LUT.0);
+//! const DGMLUTB_PARAMS: Option<&'static [u8; 64]> = Some(&BLUE_LUT.0);
+//! ```
+
+/// Number of entries in a digital gamma lookup table.
+pub const DIGITAL_GAMMA_LUT_LEN: usize = 64;
+
+/// Digital Gamma Enable parameter (Command 0xBA), `DGMEN` bit set.
+pub const DGMEN_ON: [u8; 1] = [0x04];
+
+/// Digital Gamma Enable parameter (Command 0xBA), `DGMEN` bit cleared.
+pub const DGMEN_OFF: [u8; 1] = [0x00];
+
+/// A digital gamma lookup table for one channel (DGMLUTR / DGMLUTB).
+///
+/// Entry `i` is the 8-bit output level for the `i`-th of the 64 input levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitalGammaLut(pub [u8; DIGITAL_GAMMA_LUT_LEN]);
+
+impl DigitalGammaLut {
+    /// The identity table, i.e. the power-on default. The output equals the input.
+    pub const fn linear() -> Self {
+        let mut lut = [0u8; DIGITAL_GAMMA_LUT_LEN];
+        let mut i = 0;
+        while i < DIGITAL_GAMMA_LUT_LEN {
+            lut[i] = Self::level(i);
+            i += 1;
+        }
+        Self(lut)
+    }
+
+    /// A power curve, `out = in ^ exponent` with both normalized to 0..1.
+    ///
+    /// Exponents above 1.0 darken the mid-tones of the channel, below 1.0 brighten them.
+    pub const fn gamma(exponent: f32) -> Self {
+        let mut lut = [0u8; DIGITAL_GAMMA_LUT_LEN];
+        let max = (DIGITAL_GAMMA_LUT_LEN - 1) as f32;
+        let mut i = 1;
+        while i < DIGITAL_GAMMA_LUT_LEN {
+            let x = i as f32 / max;
+            let y = exp2(exponent * log2(x));
+            lut[i] = (y * 255.0 + 0.5) as u8;
+            i += 1;
+        }
+        Self(lut)
+    }
+
+    /// Scales the channel by `numerator / denominator`, saturating at 255.
+    ///
+    /// Useful to trim the white point, e.g. `DigitalGammaLut::linear().scaled(95, 100)` lowers the
+    /// channel by 5 %.
+    pub const fn scaled(self, numerator: u16, denominator: u16) -> Self {
+        let mut lut = self.0;
+        let mut i = 0;
+        while i < DIGITAL_GAMMA_LUT_LEN {
+            let v = lut[i] as u32 * numerator as u32 / denominator as u32;
+            lut[i] = if v > 255 { 255 } else { v as u8 };
+            i += 1;
+        }
+        Self(lut)
+    }
+
+    /// Adds `offset` to every entry, saturating at 0 and 255.
+    ///
+    /// Useful to lift or lower the black level of the channel.
+    pub const fn offset(self, offset: i16) -> Self {
+        let mut lut = self.0;
+        let mut i = 0;
+        while i < DIGITAL_GAMMA_LUT_LEN {
+            let v = lut[i] as i16 + offset;
+            lut[i] = if v < 0 {
+                0
+            } else if v > 255 {
+                255
+            } else {
+                v as u8
+            };
+            i += 1;
+        }
+        Self(lut)
+    }
+
+    /// Builds a table from a function of the 8-bit input level.
+    ///
+    /// This allows arbitrary curves, e.g. using `libm` for floating point math.
+    pub fn from_fn(mut f: impl FnMut(u8) -> u8) -> Self {
+        let mut lut = [0u8; DIGITAL_GAMMA_LUT_LEN];
+        for (i, v) in lut.iter_mut().enumerate() {
+            *v = f(Self::level(i));
+        }
+        Self(lut)
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; DIGITAL_GAMMA_LUT_LEN] {
+        &self.0
+    }
+
+    /// The 8-bit level of input `i` (0-63).
+    const fn level(i: usize) -> u8 {
+        ((i * 255 + (DIGITAL_GAMMA_LUT_LEN - 1) / 2) / (DIGITAL_GAMMA_LUT_LEN - 1)) as u8
+    }
+}
+
+impl Default for DigitalGammaLut {
+    fn default() -> Self {
+        Self::linear()
+    }
+}
+
+/// Base-2 logarithm for `x > 0`, precise to about 16 fractional bits.
+const fn log2(x: f32) -> f32 {
+    let bits = x.to_bits();
+    let exponent = ((bits >> 23) & 0xFF) as i32 - 127;
+    // Mantissa in [1, 2)
+    let mut m = f32::from_bits((bits & 0x007F_FFFF) | 0x3F80_0000);
+    let mut result = exponent as f32;
+    let mut bit = 0.5;
+    let mut i = 0;
+    while i < 16 {
+        m *= m;
+        if m >= 2.0 {
+            m *= 0.5;
+            result += bit;
+        }
+        bit *= 0.5;
+        i += 1;
+    }
+    result
+}
+
+/// Base-2 exponential, accurate well beyond 8 bits of output.
+const fn exp2(x: f32) -> f32 {
+    if x < -126.0 {
+        return 0.0;
+    }
+    if x > 127.0 {
+        return f32::MAX;
+    }
+    let mut int = x as i32;
+    if (int as f32) > x {
+        int -= 1;
+    }
+    // 2^frac = e^(frac * ln 2), frac in [0, 1)
+    let t = (x - int as f32) * core::f32::consts::LN_2;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut n = 1;
+    while n < 8 {
+        term = term * t / n as f32;
+        sum += term;
+        n += 1;
+    }
+    sum * f32::from_bits(((int + 127) as u32) << 23)
+}
@@ -3,21 +3,38 @@
 use embedded_hal::digital::OutputPin;
 use embedded_hal_async::delay::DelayNs;
 
-use display_driver::bus::DisplayBus;
+use display_driver::bus::{BusRead, DisplayBus};
 use display_driver::panel::initseq::{sequenced_init, InitStep};
 use display_driver::panel::reset::{LCDResetHandler, LCDResetOption};
-use display_driver::panel::{Orientation, Panel, PanelSetBrightness};
+use display_driver::panel::shape::VisibleShape;
+use display_driver::panel::{Mirror, Orientation, Panel, PanelSetBrightness};
 
 use display_driver::{ColorFormat, DisplayError};
 
 use display_driver_mipidcs as mipidcs;
 use display_driver_mipidcs::{AddressMode, GenericMipidcs};
 
+pub mod cabc;
 pub mod consts;
+pub mod gamma;
+pub mod otp;
+pub mod rgb;
 pub mod spec;
+pub mod timing;
 
+pub use cabc::{CabcControl, CabcMode, Cace, ColorEnhancement, DisplayControl};
 use consts::*;
+pub use gamma::DigitalGammaLut;
+pub use otp::{NvmMap, OtpConfirm, OtpSequence, OtpValues};
+pub use rgb::{
+    DisplayMode, Endianness, RamAccess, RamControl, RgbBusWidth, RgbControl, RgbPolarity,
+    RgbSyncMode,
+};
 pub use spec::St7789Spec;
+pub use timing::{
+    FrameRate, FrameRateControl, FrameRateDivider, Inversion, NonDisplayLevel, PowerSave,
+    PwmFrequency,
+};
 
 /// Driver for the ST7789 display controller.
 pub struct St7789<Spec, RST, B>
@@ -59,9 +76,265 @@ where
             ) -> Result<(), B::Error>;
 
             pub async fn set_bgr_order(&mut self, bus: &mut B, bgr: bool) -> Result<(), B::Error>;
+
+            pub async fn set_idle_mode(&self, bus: &mut B, idle: bool) -> Result<(), B::Error>;
+
+            pub async fn set_partial_rows(
+                &self,
+                bus: &mut B,
+                start: u16,
+                end: u16,
+            ) -> Result<(), B::Error>;
+
+            pub async fn enter_partial_mode(&self, bus: &mut B) -> Result<(), B::Error>;
+
+            pub async fn enter_normal_mode(&self, bus: &mut B) -> Result<(), B::Error>;
         }
     }
 
+    /// Sets the Content Adaptive Brightness Control mode and color enhancement (WRCACE).
+    ///
+    /// CABC only takes effect while the brightness control block is enabled, see
+    /// [`set_display_control`](Self::set_display_control).
+    pub async fn set_cabc_mode(
+        &mut self,
+        bus: &mut B,
+        mode: CabcMode,
+        color_enhancement: ColorEnhancement,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[WRCACE], &Cace::new(mode, color_enhancement).as_bytes())
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Reads back the current CABC mode (RDCABC).
+    pub async fn read_cabc_mode(&mut self, bus: &mut B) -> Result<CabcMode, DisplayError<B::Error>>
+    where
+        B: BusRead,
+    {
+        let mut buf = [0u8; 1];
+        bus.read_data(&[RDCABC], &[], &mut buf).await?;
+        Ok(CabcMode::from_bits(buf[0]))
+    }
+
+    /// Sets the minimum brightness CABC may dim down to (WRCABCMB).
+    pub async fn set_cabc_min_brightness(
+        &mut self,
+        bus: &mut B,
+        brightness: u8,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[WRCABCMB], &[brightness])
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Sets the brightness control block, dimming and backlight flags (WRCTRLD).
+    pub async fn set_display_control(
+        &mut self,
+        bus: &mut B,
+        control: DisplayControl,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[WRCTRLD], &control.as_bytes())
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Configures the `LEDPWM` / `LEDON` outputs driven by CABC (CABCCTRL).
+    pub async fn set_cabc_control(
+        &mut self,
+        bus: &mut B,
+        control: CabcControl,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[CABCCTRL], &control.as_bytes())
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Uploads the red and blue digital gamma tables and enables digital gamma.
+    pub async fn set_digital_gamma(
+        &mut self,
+        bus: &mut B,
+        red: &DigitalGammaLut,
+        blue: &DigitalGammaLut,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[DGMLUTR], red.as_bytes())
+            .await?;
+        bus.write_cmd_with_params(&[DGMLUTB], blue.as_bytes())
+            .await?;
+        self.set_digital_gamma_enabled(bus, true).await
+    }
+
+    /// Enables or disables digital gamma (DGMEN), keeping the uploaded tables.
+    pub async fn set_digital_gamma_enabled(
+        &mut self,
+        bus: &mut B,
+        enabled: bool,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let param = if enabled {
+            &gamma::DGMEN_ON
+        } else {
+            &gamma::DGMEN_OFF
+        };
+        bus.write_cmd_with_params(&[DGMEN], param)
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Selects the display operation mode, RAM access interface and data format (RAMCTRL).
+    ///
+    /// Switching to [`RamControl::rgb_interface`] hands pixel data over to the RGB interface, after
+    /// which [`write_pixels`](display_driver::DisplayDriver::write_pixels) has no visible effect.
+    pub async fn set_ram_control(
+        &mut self,
+        bus: &mut B,
+        control: RamControl,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[RAMCTRL], control.as_bytes())
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Sets the RGB interface sync mode, signal polarities, porches and RAM bypass (RGBCTRL).
+    pub async fn set_rgb_control(
+        &mut self,
+        bus: &mut B,
+        control: RgbControl,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[RGBCTRL], control.as_bytes())
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Sets the frame rate in normal mode (FRCTRL2).
+    pub async fn set_normal_frame_rate(
+        &mut self,
+        bus: &mut B,
+        rate: FrameRate,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[FRCTRL2], &rate.as_bytes())
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Sets the frame rate divider and frame rates in idle and partial mode (FRCTRL1).
+    pub async fn set_frame_rate_control(
+        &mut self,
+        bus: &mut B,
+        control: FrameRateControl,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[FRCTRL1], control.as_bytes())
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Sets the source output level in the non-display area of partial mode (PARCTRL).
+    pub async fn set_partial_control(
+        &mut self,
+        bus: &mut B,
+        level: NonDisplayLevel,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[PARCTRL], &level.as_bytes())
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Enables power saving in normal and/or idle mode (PWRSAVCTRL).
+    ///
+    /// Note: Avalible in P3, Not Avalible in V, VW
+    pub async fn set_power_save(
+        &mut self,
+        bus: &mut B,
+        power_save: PowerSave,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[PWRSAVCTRL], &power_save.as_bytes())
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Enables or disables power saving while the display is off (DLPOFFSAVE).
+    ///
+    /// Note: Avalible in VW, Not Avalible in V, P3
+    pub async fn set_display_off_power_save(
+        &mut self,
+        bus: &mut B,
+        enabled: bool,
+    ) -> Result<(), DisplayError<B::Error>> {
+        let param = if enabled {
+            &timing::DLPOFFSAVE_ON
+        } else {
+            &timing::DLPOFFSAVE_OFF
+        };
+        bus.write_cmd_with_params(&[DLPOFFSAVE], param)
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Sets the frequency of the CABC `LEDPWM` output (PWMFRSEL).
+    pub async fn set_pwm_frequency(
+        &mut self,
+        bus: &mut B,
+        frequency: PwmFrequency,
+    ) -> Result<(), DisplayError<B::Error>> {
+        bus.write_cmd_with_params(&[PWMFRSEL], &frequency.as_bytes())
+            .await
+            .map_err(DisplayError::BusError)
+    }
+
+    /// Burns the VCOM offset and/or module ID into OTP memory. This cannot be undone.
+    ///
+    /// Each command is passed to `on_step` before it is sent, e.g. for logging. After programming,
+    /// the controller is reset to reload the values from NVM, and the module ID is read back with
+    /// RDID1-RDID3 and compared. A mismatch returns [`DisplayError::VerifyFailed`]. VCMOFSET is
+    /// write-only, so the VCOM offset is not verified.
+    ///
+    /// The reset leaves the controller uninitialized, so [`Panel::init`] has to be called again.
+    /// Returns [`DisplayError::Unsupported`] if [`St7789Spec::NVM_MAP`] is not set.
+    pub async fn program_otp<D: DelayNs>(
+        &mut self,
+        bus: &mut B,
+        mut delay: D,
+        values: OtpValues,
+        _confirm: OtpConfirm,
+        mut on_step: impl FnMut(&InitStep<'_>),
+    ) -> Result<(), DisplayError<B::Error>>
+    where
+        B: BusRead,
+    {
+        let map = Spec::NVM_MAP.ok_or(DisplayError::Unsupported)?;
+        let sequence = OtpSequence::new(values, &map);
+        for step in sequence.steps() {
+            on_step(&step);
+            sequenced_init(core::iter::once(step), &mut delay, bus).await?;
+        }
+
+        if let Some(id) = values.module_id {
+            for (cmd, expected) in [RDID1, RDID2, RDID3].into_iter().zip(id) {
+                let mut buf = [0u8; 1];
+                bus.read_data(&[cmd], &[], &mut buf).await?;
+                if buf[0] != expected {
+                    return Err(DisplayError::VerifyFailed);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Passes the OTP programming sequence of [`program_otp`](Self::program_otp) to `on_step`
+    /// without sending anything.
+    ///
+    /// Returns [`DisplayError::Unsupported`] if [`St7789Spec::NVM_MAP`] is not set.
+    pub fn program_otp_dry_run(
+        &self,
+        values: OtpValues,
+        mut on_step: impl FnMut(&InitStep<'_>),
+    ) -> Result<(), DisplayError<B::Error>> {
+        let map = Spec::NVM_MAP.ok_or(DisplayError::Unsupported)?;
+        OtpSequence::new(values, &map)
+            .steps()
+            .for_each(|step| on_step(&step));
+        Ok(())
+    }
+
     /// Initialization sequence for ST7789.
     const INIT_STEPS: &'static [InitStep<'static>] = &[
         // Sleep Out
@@ -72,6 +345,9 @@ where
             mipidcs::SET_PIXEL_FORMAT,
             &mipidcs::PixelFormat::dbi_and_dpi(mipidcs::PixelFormatType::Bits16).as_bytes(),
         ),
+        // RAM Control and RGB Interface Control (Optional)
+        InitStep::maybe_cmd_with(RAMCTRL, Spec::RAMCTRL_PARAMS),
+        InitStep::maybe_cmd_with(RGBCTRL, Spec::RGBCTRL_PARAMS),
         // Porch Setting
         InitStep::CommandWithParams(PORCTRL, &Spec::PORCTRL_PARAMS),
         // Gate Control
@@ -88,6 +364,14 @@ where
         InitStep::CommandWithParams(VDVS, &[Spec::VDVS_PARAM]),
         // Frame Rate Control
         InitStep::CommandWithParams(FRCTRL2, &[Spec::FRCTRL2_PARAM]),
+        // Idle / Partial Frame Rate and Partial Control (Optional)
+        InitStep::maybe_cmd_with(FRCTRL1, Spec::FRCTRL1_PARAMS),
+        InitStep::maybe_cmd_with(PARCTRL, Spec::PARCTRL_PARAMS),
+        // Power Saving (Optional)
+        InitStep::maybe_cmd_with(PWRSAVCTRL, Spec::PWRSAVCTRL_PARAMS),
+        InitStep::maybe_cmd_with(DLPOFFSAVE, Spec::DLPOFFSAVE_PARAMS),
+        // PWM Frequency (Optional)
+        InitStep::maybe_cmd_with(PWMFRSEL, Spec::PWMFRSEL_PARAMS),
         // Power Control 1
         InitStep::CommandWithParams(PWCTRL1, &Spec::PWCTRL1_PARAMS),
         // Power Control 2 (Optional)
@@ -99,6 +383,14 @@ where
         // Gamma
         InitStep::CommandWithParams(PVGAMCTRL, &Spec::PVGAMCTRL_PARAMS),
         InitStep::CommandWithParams(NVGAMCTRL, &Spec::NVGAMCTRL_PARAMS),
+        // Digital Gamma (Optional)
+        InitStep::maybe_cmd_with(DGMLUTR, Spec::DGMLUTR_PARAMS),
+        InitStep::maybe_cmd_with(DGMLUTB, Spec::DGMLUTB_PARAMS),
+        if Spec::DGMLUTR_PARAMS.is_some() || Spec::DGMLUTB_PARAMS.is_some() {
+            InitStep::CommandWithParams(DGMEN, &gamma::DGMEN_ON)
+        } else {
+            InitStep::Nop
+        },
         // Invert Mode
         InitStep::select_cmd(
             Spec::INVERTED,
@@ -107,15 +399,9 @@ where
         ),
         InitStep::CommandWithParams(
             mipidcs::SET_ADDRESS_MODE,
-            &[if Spec::BGR {
-                AddressMode::BGR.bits()
-            } else {
-                0u8
-            }],
+            &[AddressMode::initial::<Spec>().bits()],
         ),
-        // Display On
-        InitStep::SingleCommand(mipidcs::SET_DISPLAY_ON),
-        InitStep::DelayMs(120),
+        // Display On is sent later by the driver, after the frame memory has been prepared.
     ];
 }
 
@@ -127,8 +413,14 @@ where
 {
     const CMD_LEN: usize = 1;
     const PIXEL_WRITE_CMD: [u8; 4] = [mipidcs::WRITE_MEMORY_START, 0, 0, 0];
+    const DISPLAY_ON_DELAY_MS: u8 = 120;
+    const POWER_OFF_DELAY_MS: u8 = Spec::POWER_OFF_DELAY_MS;
 
-    async fn init<D: DelayNs>(&mut self, bus: &mut B, mut delay: D) -> Result<(), B::Error> {
+    async fn init<D: DelayNs>(
+        &mut self,
+        bus: &mut B,
+        mut delay: D,
+    ) -> Result<(), DisplayError<B::Error>> {
         // Hardware Reset
         let mut reseter = LCDResetHandler::new(
             &mut self.inner.reset_pin,
@@ -139,13 +431,25 @@ where
             Some(&[mipidcs::SOFT_RESET]),
         );
         reseter.reset().await?;
+        self.inner.reset_address_mode();
 
         // Execute Initialization Sequence
         // copied() only copies the items during iteration; it does not copy the entire sequence
-        sequenced_init(Self::INIT_STEPS.iter().copied(), &mut delay, bus).await
+        sequenced_init(Self::INIT_STEPS.iter().copied(), &mut delay, bus)
+            .await
+            .map_err(DisplayError::BusError)
     }
 
     delegate::delegate! {
+        to &mut self.inner {
+            #[through(Panel::<B>)]
+            async fn set_display_on(
+                &mut self,
+                bus: &mut B,
+                on: bool,
+            ) -> Result<(), DisplayError<B::Error>>;
+        }
+
         to self.inner {
             fn width(&self) -> u16;
 
@@ -153,6 +457,8 @@ where
 
             fn size(&self) -> (u16, u16);
 
+            fn visible_shape(&self) -> VisibleShape;
+
             async fn set_window(
                 &mut self,
                 bus: &mut B,
@@ -173,6 +479,19 @@ where
                 bus: &mut B,
                 orientation: Orientation,
             ) -> Result<(), DisplayError<B::Error>>;
+
+            async fn set_mirror(
+                &mut self,
+                bus: &mut B,
+                mirror: Mirror,
+            ) -> Result<(), DisplayError<B::Error>>;
+
+            async fn set_sleep<D: DelayNs>(
+                &mut self,
+                bus: &mut B,
+                sleep: bool,
+                delay: D,
+            ) -> Result<(), DisplayError<B::Error>>;
         }
     }
 }
@@ -183,6 +502,10 @@ where
     RST: OutputPin,
     B: DisplayBus,
 {
+    /// Sets the display brightness (WRDISBV).
+    ///
+    /// This drives the `LEDPWM` output, and only takes effect while the brightness control block is
+    /// enabled, see [`St7789::set_display_control`].
     async fn set_brightness(
         &mut self,
         bus: &mut B,
@@ -0,0 +1,140 @@
+//! One-time programmable (OTP) memory programming (Commands 0xC1, 0xC5, 0xFA, 0xFC, 0xFE).
+//!
+//! The ST7789 can burn the VCOM offset and the module ID into its NVM, so they are loaded on every
+//! reset. Programming is irreversible, so the API is guarded:
+//! - [`St7789::program_otp`](crate::St7789::program_otp) requires an [`OtpConfirm`] token.
+//! - [`St7789::program_otp_dry_run`](crate::St7789::program_otp_dry_run) only reports the command
+//!   sequence, so it can be logged and checked against the datasheet of the module first.
+//!
+//! The NVM addresses differ between controller variants, so they come from
+//! [`St7789Spec::NVM_MAP`](crate::St7789Spec::NVM_MAP), which none of the bundled specs set.
+//!
+//! Only the module ID can be verified after programming, by reading it back with RDID1-RDID3.
+//! VCMOFSET is write-only.
+
+use display_driver::panel::initseq::InitStep;
+use display_driver_mipidcs as mipidcs;
+
+use crate::consts::*;
+
+/// Program Mode Enable parameters (Command 0xFA), entering program mode.
+pub const PROMEN_ENTER: [u8; 4] = [0x5A, 0x69, 0xEE, 0x04];
+
+/// Program Mode Enable parameters (Command 0xFA), leaving program mode.
+pub const PROMEN_EXIT: [u8; 4] = [0x5A, 0x69, 0xEE, 0x00];
+
+/// Program Action parameters (Command 0xFE), burning the value set by NVMSET.
+pub const PROMACT_PARAMS: [u8; 2] = [0x29, 0xA5];
+
+/// Time to wait after each Program Action.
+pub const PROGRAM_DELAY_MS: u8 = 100;
+
+/// Maximum number of steps in an OTP programming sequence.
+const MAX_STEPS: usize = 20;
+
+/// Confirmation that OTP memory is about to be burned irreversibly.
+///
+/// Required by [`St7789::program_otp`](crate::St7789::program_otp).
+#[derive(Debug)]
+pub struct OtpConfirm {
+    _private: (),
+}
+
+impl OtpConfirm {
+    /// Confirms that the OTP memory will be burned, and that this cannot be undone.
+    pub const fn irreversible() -> Self {
+        Self { _private: () }
+    }
+}
+
+/// NVM addresses of the programmable values, from the datasheet of the controller variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NvmMap {
+    /// Address of the VCOM offset (VCMOFSET).
+    pub vcom_offset: u8,
+    /// Addresses of ID1, ID2 and ID3 (IDSET).
+    pub id: [u8; 3],
+}
+
+/// The values to burn into OTP memory. `None` leaves the value unprogrammed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OtpValues {
+    /// VCOM offset (VCMOFSET).
+    pub vcom_offset: Option<u8>,
+    /// Module ID: ID1, ID2, ID3 (IDSET), read back with RDID1-RDID3.
+    pub module_id: Option<[u8; 3]>,
+}
+
+/// The command sequence that programs a set of [`OtpValues`].
+///
+/// The values are written to their registers first, then each byte is burned with NVMSET and
+/// PROMACT. Finally, a software reset reloads the registers from NVM, so the module ID can be read
+/// back for verification.
+#[derive(Debug, Clone)]
+pub struct OtpSequence {
+    values: OtpValues,
+    nvmset: [[u8; 2]; 4],
+    nvmset_len: usize,
+}
+
+impl OtpSequence {
+    /// Creates the sequence for `values`, using the NVM addresses in `map`.
+    pub fn new(values: OtpValues, map: &NvmMap) -> Self {
+        let mut nvmset = [[0u8; 2]; 4];
+        let mut nvmset_len = 0;
+        if let Some(offset) = values.vcom_offset {
+            nvmset[nvmset_len] = [map.vcom_offset, offset];
+            nvmset_len += 1;
+        }
+        if let Some(id) = values.module_id {
+            for (address, value) in map.id.iter().zip(id) {
+                nvmset[nvmset_len] = [*address, value];
+                nvmset_len += 1;
+            }
+        }
+        Self {
+            values,
+            nvmset,
+            nvmset_len,
+        }
+    }
+
+    /// Returns the values to be programmed.
+    pub fn values(&self) -> &OtpValues {
+        &self.values
+    }
+
+    /// Returns the steps of the sequence, in order.
+    pub fn steps(&self) -> impl Iterator<Item = InitStep<'_>> {
+        let mut steps = [InitStep::Nop; MAX_STEPS];
+        let mut len = 0;
+        let mut push = |step| {
+            steps[len] = step;
+            len += 1;
+        };
+
+        if let Some(offset) = &self.values.vcom_offset {
+            push(InitStep::CommandWithParams(
+                VCMOFSET,
+                core::slice::from_ref(offset),
+            ));
+        }
+        if let Some(id) = &self.values.module_id {
+            push(InitStep::CommandWithParams(IDSET, id));
+        }
+
+        push(InitStep::CommandWithParams(PROMEN, &PROMEN_ENTER));
+        for params in &self.nvmset[..self.nvmset_len] {
+            push(InitStep::CommandWithParams(NVMSET, params));
+            push(InitStep::CommandWithParams(PROMACT, &PROMACT_PARAMS));
+            push(InitStep::DelayMs(PROGRAM_DELAY_MS));
+        }
+        push(InitStep::CommandWithParams(PROMEN, &PROMEN_EXIT));
+
+        // Reload the registers from NVM
+        push(InitStep::SingleCommand(mipidcs::SOFT_RESET));
+        push(InitStep::DelayMs(120));
+
+        steps.into_iter().take(len)
+    }
+}
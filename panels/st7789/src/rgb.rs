@@ -0,0 +1,192 @@
+//! RGB (DPI) interface configuration (Commands 0xB0, 0xB1).
+//!
+//! The ST7789 can be initialized over SPI and then receive pixel data over its parallel RGB
+//! interface, e.g. from an LTDC peripheral. In this case the RAM Control selects where pixel data
+//! comes from, and the RGB Interface Control sets the sync signals and porches.
+//!
+//! The parameters are built with `const fn`, so they can be used in a [`St7789Spec`](crate::St7789Spec):
+//! ```ignore
+//! const RAMCTRL_PARAMS: Option<&'static [u8; 2]> = Some(&RamControl::rgb_interface().0);
+//! const RGBCTRL_PARAMS: Option<&'static [u8; 3]> =
+//!     Some(&RgbControl::new(RgbSyncMode::DataEnable).with_porches(2, 20).0);
+//! ```
+
+use bitflags::bitflags;
+
+/// Display operation mode, the `DM[1:0]` bits of RAM Control (Command 0xB0).
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayMode {
+    /// Display operation synchronized to the internal clock (MCU interface).
+    Mcu = 0b00,
+    /// Display operation synchronized to the RGB interface signals.
+    Rgb = 0b01,
+    /// Display operation synchronized to the VSYNC signal.
+    Vsync = 0b10,
+}
+
+/// RAM access interface, the `RM` bit of Command 0xB0.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RamAccess {
+    /// Frame memory is written through the MCU interface (SPI / 8080).
+    Mcu = 0x00,
+    /// Frame memory is written through the RGB interface.
+    Rgb = 0x10,
+}
+
+/// Byte order of 16-bit pixel data on the MCU interface, the `ENDIAN` bit of Command 0xB0.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    /// MSB first (default).
+    Big = 0x00,
+    /// LSB first.
+    Little = 0x08,
+}
+
+/// RGB interface bus width, the `RIM` bit of Command 0xB0.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RgbBusWidth {
+    /// 16- or 18-bit bus, one pixel per clock (default).
+    Parallel = 0x00,
+    /// 6-bit bus, one color component per clock.
+    Bits6 = 0x04,
+}
+
+/// RAM Control parameters (Command 0xB0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RamControl(pub [u8; 2]);
+
+impl RamControl {
+    /// Second parameter fixed bits and power-on default (`EPF` = 11b).
+    const PARAM2_DEFAULT: u8 = 0xF0;
+
+    pub const fn new(display_mode: DisplayMode, ram_access: RamAccess) -> Self {
+        Self([display_mode as u8 | ram_access as u8, Self::PARAM2_DEFAULT])
+    }
+
+    /// The power-on default: MCU interface for both display operation and RAM access.
+    pub const fn mcu_interface() -> Self {
+        Self::new(DisplayMode::Mcu, RamAccess::Mcu)
+    }
+
+    /// Display operation and RAM access through the RGB interface.
+    pub const fn rgb_interface() -> Self {
+        Self::new(DisplayMode::Rgb, RamAccess::Rgb)
+    }
+
+    pub const fn with_endianness(self, endianness: Endianness) -> Self {
+        Self([
+            self.0[0],
+            (self.0[1] & !(Endianness::Little as u8)) | endianness as u8,
+        ])
+    }
+
+    pub const fn with_rgb_bus_width(self, width: RgbBusWidth) -> Self {
+        Self([
+            self.0[0],
+            (self.0[1] & !(RgbBusWidth::Bits6 as u8)) | width as u8,
+        ])
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; 2] {
+        &self.0
+    }
+}
+
+impl Default for RamControl {
+    fn default() -> Self {
+        Self::mcu_interface()
+    }
+}
+
+/// RGB interface sync mode, the `RCM[1:0]` bits of RGB Interface Control (Command 0xB1).
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RgbSyncMode {
+    /// Pixel data is latched while the data enable (DE) signal is active (default).
+    DataEnable = 0x40,
+    /// Pixel data position is derived from HSYNC / VSYNC and the porches, DE is ignored.
+    HvSync = 0x60,
+}
+
+bitflags! {
+    /// RGB interface signal polarities, the low bits of Command 0xB1.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct RgbPolarity: u8 {
+        /// B0 - Enable Polarity (EPL)
+        ///
+        /// 0 = DE active high, 1 = DE active low.
+        const ENABLE_ACTIVE_LOW = 0b0000_0001;
+
+        /// B1 - Dot Clock Polarity (DPL)
+        ///
+        /// 0 = Data latched on the rising edge of PCLK, 1 = on the falling edge.
+        const PCLK_FALLING_EDGE = 0b0000_0010;
+
+        /// B2 - HSYNC Polarity (HSPL)
+        ///
+        /// 0 = Active low, 1 = Active high.
+        const HSYNC_ACTIVE_HIGH = 0b0000_0100;
+
+        /// B3 - VSYNC Polarity (VSPL)
+        ///
+        /// 0 = Active low, 1 = Active high.
+        const VSYNC_ACTIVE_HIGH = 0b0000_1000;
+    }
+}
+
+/// RGB Interface Control parameters (Command 0xB1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RgbControl(pub [u8; 3]);
+
+impl RgbControl {
+    /// `WO` bit: pixel data bypasses the frame memory (direct to shift register).
+    const RAM_BYPASS: u8 = 0x80;
+
+    /// Creates the parameters with the default polarities and porches (VBP = 2, HBP = 20).
+    pub const fn new(sync_mode: RgbSyncMode) -> Self {
+        Self([sync_mode as u8, 0x02, 0x14])
+    }
+
+    /// Writes pixel data directly to the display, bypassing the frame memory.
+    ///
+    /// This saves the RAM write but requires the host to refresh the panel continuously.
+    pub const fn with_ram_bypass(self, bypass: bool) -> Self {
+        let p1 = if bypass {
+            self.0[0] | Self::RAM_BYPASS
+        } else {
+            self.0[0] & !Self::RAM_BYPASS
+        };
+        Self([p1, self.0[1], self.0[2]])
+    }
+
+    pub const fn with_polarity(self, polarity: RgbPolarity) -> Self {
+        Self([
+            (self.0[0] & !RgbPolarity::all().bits()) | polarity.bits(),
+            self.0[1],
+            self.0[2],
+        ])
+    }
+
+    /// Sets the vertical (0-127 lines) and horizontal (0-31 clocks) back porch.
+    ///
+    /// Only used in [`RgbSyncMode::HvSync`]. Values are clamped to the valid range.
+    pub const fn with_porches(self, vbp: u8, hbp: u8) -> Self {
+        let vbp = if vbp > 0x7F { 0x7F } else { vbp };
+        let hbp = if hbp > 0x1F { 0x1F } else { hbp };
+        Self([self.0[0], vbp, hbp])
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; 3] {
+        &self.0
+    }
+}
+
+impl Default for RgbControl {
+    fn default() -> Self {
+        Self::new(RgbSyncMode::DataEnable)
+    }
+}
@@ -1,5 +1,7 @@
 pub use display_driver_mipidcs::PanelSpec;
 
+use crate::otp::NvmMap;
+
 pub mod generic;
 pub mod vendor_specs;
 
@@ -45,4 +47,49 @@ pub trait St7789Spec: PanelSpec {
     ///
     /// Note: Avalible in P3, Not Avalible in V, VW
     const GATESEL_PARAMS: Option<&'static [u8; 1]> = None;
+
+    /// Digital Gamma Look-up Table for Red (0xE2) - 64 bytes
+    ///
+    /// Digital gamma is enabled if either table is set, see [`DigitalGammaLut`](crate::DigitalGammaLut).
+    const DGMLUTR_PARAMS: Option<&'static [u8; 64]> = None;
+
+    /// Digital Gamma Look-up Table for Blue (0xE3) - 64 bytes
+    const DGMLUTB_PARAMS: Option<&'static [u8; 64]> = None;
+
+    /// RAM Control (0xB0) - 2 bytes
+    ///
+    /// Set this to use the RGB interface, see [`RamControl`](crate::RamControl).
+    const RAMCTRL_PARAMS: Option<&'static [u8; 2]> = None;
+
+    /// RGB Interface Control (0xB1) - 3 bytes
+    ///
+    /// See [`RgbControl`](crate::RgbControl).
+    const RGBCTRL_PARAMS: Option<&'static [u8; 3]> = None;
+
+    /// Frame Rate Control 1 (0xB3), in partial mode / idle colors - 3 bytes
+    ///
+    /// See [`FrameRateControl`](crate::FrameRateControl).
+    const FRCTRL1_PARAMS: Option<&'static [u8; 3]> = None;
+
+    /// Partial Control (0xB5) - 1 byte
+    const PARCTRL_PARAMS: Option<&'static [u8; 1]> = None;
+
+    /// Power Saving Control (0xB6) - 1 byte
+    ///
+    /// Note: Avalible in P3, Not Avalible in V, VW
+    const PWRSAVCTRL_PARAMS: Option<&'static [u8; 1]> = None;
+
+    /// Display off power save (0xBD) - 1 byte
+    ///
+    /// Note: Avalible in VW, Not Avalible in V, P3
+    const DLPOFFSAVE_PARAMS: Option<&'static [u8; 1]> = None;
+
+    /// PWM Frequency Selection (0xCC) - 1 byte
+    const PWMFRSEL_PARAMS: Option<&'static [u8; 1]> = None;
+
+    /// NVM addresses for OTP programming, see [`St7789::program_otp`](crate::St7789::program_otp).
+    ///
+    /// OTP programming is unsupported if this is `None`. None of the bundled specs set it, the
+    /// addresses have to be taken from the datasheet of the controller variant.
+    const NVM_MAP: Option<NvmMap> = None;
 }
@@ -0,0 +1,168 @@
+//! Frame rate, partial mode and power saving parameters
+//! (Commands 0xB3, 0xB5, 0xB6, 0xBD, 0xC6, 0xCC).
+//!
+//! Lowering the frame rate in idle and partial mode is what makes these modes save power, since
+//! the source drivers are toggled less often.
+
+use bitflags::bitflags;
+
+/// Source inversion mode, the `NL[2:0]` bits of the frame rate parameters.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inversion {
+    /// Dot inversion (default).
+    Dot = 0x00,
+    /// Column inversion, uses less power.
+    Column = 0xE0,
+}
+
+/// Frame rate in one display mode, `NL[2:0]` and `RTN[4:0]` (Commands 0xB3 and 0xC6).
+///
+/// The frame rate is given by the number of clocks per line (`RTN`), assuming the default porches
+/// (PORCTRL) and 320 lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameRate(pub u8);
+
+impl FrameRate {
+    /// Frame rates in Hz for `RTN` = 0x00..=0x1F.
+    const RATES_HZ: [u8; 32] = [
+        119, 111, 105, 99, 94, 90, 86, 82, 78, 75, 72, 69, 67, 64, 62, 60, 58, 57, 55, 53, 52, 50,
+        49, 48, 46, 45, 44, 43, 42, 41, 40, 39,
+    ];
+
+    /// Creates the parameter from a raw `RTN` value (0x00-0x1F).
+    pub const fn new(rtn: u8, inversion: Inversion) -> Self {
+        Self((rtn & 0x1F) | inversion as u8)
+    }
+
+    /// Picks the `RTN` value closest to `hz` (39-119 Hz).
+    pub const fn from_hz(hz: u8, inversion: Inversion) -> Self {
+        let mut best = 0;
+        let mut i = 1;
+        while i < Self::RATES_HZ.len() {
+            if Self::RATES_HZ[i].abs_diff(hz) < Self::RATES_HZ[best].abs_diff(hz) {
+                best = i;
+            }
+            i += 1;
+        }
+        Self::new(best as u8, inversion)
+    }
+
+    /// The frame rate in Hz, before any divider is applied.
+    pub const fn hz(&self) -> u8 {
+        Self::RATES_HZ[(self.0 & 0x1F) as usize]
+    }
+
+    pub const fn as_bytes(self) -> [u8; 1] {
+        [self.0]
+    }
+}
+
+impl Default for FrameRate {
+    /// 60 Hz, dot inversion.
+    fn default() -> Self {
+        Self::new(0x0F, Inversion::Dot)
+    }
+}
+
+/// Clock divider in idle and partial mode, the `DIV[1:0]` bits of Command 0xB3.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameRateDivider {
+    Div1 = 0b00,
+    Div2 = 0b01,
+    Div4 = 0b10,
+    Div8 = 0b11,
+}
+
+/// Frame Rate Control 1 parameters (Command 0xB3), for idle and partial mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameRateControl(pub [u8; 3]);
+
+impl FrameRateControl {
+    /// `FRSEN` bit: use separate frame rates in partial and idle mode.
+    const SEPARATE: u8 = 0x10;
+
+    /// Idle and partial mode share the normal mode frame rate (FRCTRL2), divided by `divider`.
+    pub const fn new(divider: FrameRateDivider) -> Self {
+        let default = FrameRate::new(0x0F, Inversion::Dot).0;
+        Self([divider as u8, default, default])
+    }
+
+    /// Uses separate frame rates for partial and idle mode.
+    pub const fn with_separate_rates(self, partial: FrameRate, idle: FrameRate) -> Self {
+        Self([self.0[0] | Self::SEPARATE, partial.0, idle.0])
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; 3] {
+        &self.0
+    }
+}
+
+impl Default for FrameRateControl {
+    fn default() -> Self {
+        Self::new(FrameRateDivider::Div1)
+    }
+}
+
+/// Source output level in the non-display area of partial mode, the `NDL` bit of Command 0xB5.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonDisplayLevel {
+    /// V63, i.e. white on normally white panels (default).
+    V63 = 0x00,
+    /// V0.
+    V0 = 0x80,
+}
+
+impl NonDisplayLevel {
+    pub const fn as_bytes(self) -> [u8; 1] {
+        [self as u8]
+    }
+}
+
+bitflags! {
+    /// Power Saving Control flags (Command 0xB6).
+    ///
+    /// Note: Avalible in P3, Not Avalible in V, VW
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct PowerSave: u8 {
+        /// B0 - Power saving in idle mode (IS).
+        const IDLE = 0b0000_0001;
+
+        /// B1 - Power saving in normal mode (NS).
+        const NORMAL = 0b0000_0010;
+    }
+}
+
+impl PowerSave {
+    pub const fn as_bytes(&self) -> [u8; 1] {
+        [self.bits()]
+    }
+}
+
+/// Display off power save parameter (Command 0xBD), `DOFSAVE` bit set.
+///
+/// Note: Avalible in VW, Not Avalible in V, P3
+pub const DLPOFFSAVE_ON: [u8; 1] = [0x01];
+
+/// Display off power save parameter (Command 0xBD), `DOFSAVE` bit cleared.
+pub const DLPOFFSAVE_OFF: [u8; 1] = [0x00];
+
+/// PWM Frequency Selection parameter (Command 0xCC), for the CABC `LEDPWM` output.
+///
+/// Both fields divide the internal oscillator, larger values give a lower frequency. See the
+/// frequency table in the datasheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PwmFrequency(pub u8);
+
+impl PwmFrequency {
+    /// Creates the parameter from the `CS[2:0]` and `CLK[2:0]` fields.
+    pub const fn new(cs: u8, clk: u8) -> Self {
+        Self(((cs & 0x07) << 3) | (clk & 0x07))
+    }
+
+    pub const fn as_bytes(self) -> [u8; 1] {
+        [self.0]
+    }
+}